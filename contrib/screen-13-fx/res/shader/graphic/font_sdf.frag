@@ -0,0 +1,25 @@
+#version 460 core
+
+layout(constant_id = 0) const uint NUM_PAGES = 1;
+
+layout(push_constant) uniform PushConstants {
+    layout(offset = 80) vec4 glyph_color;
+    layout(offset = 96) vec4 outline_color;
+    layout(offset = 112) float threshold;
+    layout(offset = 116) float smoothing;
+} push_constants;
+
+layout(set = 0, binding = 0) uniform sampler2D pages_sampler[NUM_PAGES];
+
+layout(location = 0) in vec2 texcoord;
+layout(location = 1) in flat int page_in;
+
+layout(location = 0) out vec4 color;
+
+void main() {
+    float distance = texture(pages_sampler[page_in], texcoord).r;
+    float alpha = smoothstep(push_constants.threshold - push_constants.smoothing,
+                             push_constants.threshold + push_constants.smoothing,
+                             distance);
+    color = vec4(push_constants.glyph_color.rgb, push_constants.glyph_color.a * alpha);
+}
@@ -23,20 +23,67 @@ fn color_to_unorm(color: Color) -> [u8; 16] {
 #[derive(Debug)]
 pub struct BitmapFont {
     cache: HashPool,
+    distance_range: Option<f32>,
     font: BMFont,
     pages: Vec<Arc<Image>>,
     pipeline: Arc<GraphicPipeline>,
 }
 
 impl BitmapFont {
+    /// The distance value which lies exactly on the edge of a signed-distance-field glyph.
+    pub const SDF_THRESHOLD: f32 = 0.5;
+
     pub fn new(
         device: &Arc<Device>,
         font: BMFont,
         pages: impl Into<Vec<Arc<Image>>>,
+    ) -> anyhow::Result<Self> {
+        Self::create(device, font, pages.into(), None)
+    }
+
+    /// Creates a font from signed-distance-field glyph pages.
+    ///
+    /// Each page must store the distance to the glyph edge in the red channel, where
+    /// [`BitmapFont::SDF_THRESHOLD`] lies on the edge and `distance_range` is the number of page
+    /// pixels covered by the full `0.0..=1.0` range of distance values. Glyphs rendered this way
+    /// remain crisp at any scale.
+    ///
+    /// **_NOTE:_** Outline colors are not supported by signed-distance-field fonts.
+    pub fn new_sdf(
+        device: &Arc<Device>,
+        font: BMFont,
+        pages: impl Into<Vec<Arc<Image>>>,
+        distance_range: f32,
+    ) -> anyhow::Result<Self> {
+        debug_assert!(distance_range > 0.0);
+
+        Self::create(device, font, pages.into(), Some(distance_range))
+    }
+
+    fn create(
+        device: &Arc<Device>,
+        font: BMFont,
+        pages: Vec<Arc<Image>>,
+        distance_range: Option<f32>,
     ) -> anyhow::Result<Self> {
         let cache = HashPool::new(device);
-        let pages = pages.into();
         let num_pages = pages.len() as u32;
+        let specialization_info = SpecializationInfo::new(
+            [vk::SpecializationMapEntry {
+                constant_id: 0,
+                offset: 0,
+                size: 4,
+            }],
+            num_pages.to_ne_bytes(),
+        );
+        let fragment_shader = if distance_range.is_some() {
+            Shader::new_fragment(
+                include_spirv!("res/shader/graphic/font_sdf.frag", frag).as_slice(),
+            )
+            .image_sampler(0, SamplerInfo::LINEAR)
+        } else {
+            Shader::new_fragment(include_spirv!("res/shader/graphic/font.frag", frag).as_slice())
+        };
         let pipeline = Arc::new(
             GraphicPipeline::create(
                 device,
@@ -45,17 +92,7 @@ impl BitmapFont {
                     Shader::new_vertex(
                         include_spirv!("res/shader/graphic/font.vert", vert).as_slice(),
                     ),
-                    Shader::new_fragment(
-                        include_spirv!("res/shader/graphic/font.frag", frag).as_slice(),
-                    )
-                    .specialization_info(SpecializationInfo::new(
-                        [vk::SpecializationMapEntry {
-                            constant_id: 0,
-                            offset: 0,
-                            size: 4,
-                        }],
-                        num_pages.to_ne_bytes(),
-                    )),
+                    fragment_shader.specialization_info(specialization_info),
                 ],
             )
             .context("Unable to create bitmap font pipeline")?,
@@ -63,12 +100,24 @@ impl BitmapFont {
 
         Ok(Self {
             cache,
+            distance_range,
             font,
             pages,
             pipeline,
         })
     }
 
+    /// Returns the number of page pixels covered by the distance values of a
+    /// signed-distance-field font, or `None` for regular bitmap fonts.
+    pub fn distance_range(&self) -> Option<f32> {
+        self.distance_range
+    }
+
+    /// Returns `true` if this font was created from signed-distance-field glyph pages.
+    pub fn is_sdf(&self) -> bool {
+        self.distance_range.is_some()
+    }
+
     // TODO: Add description and example showing layout area, top/bottom explanation, etc
     /// Returns the position and area, in pixels, required to render the given text.
     ///
@@ -216,21 +265,53 @@ impl BitmapFont {
             pass = pass.read_descriptor((0, [idx as _]), *page_node);
         }
 
+        let push_constants = Self::push_constants(
+            transform,
+            [image_info.width, image_info.height],
+            &color,
+            self.distance_range
+                .map(|distance_range| distance_range * scale),
+        );
+
         pass.record_subpass(move |subpass, _| {
             if let Some((x, y, width, height)) = scissor {
                 subpass.set_scissor(x, y, width, height);
             }
 
+            // Regular fonts do not declare the signed-distance-field values, which are skipped
             subpass
-                .push_constants(cast_slice(&transform.to_cols_array()))
-                .push_constants_offset(64, &(1.0 / image_info.width as f32).to_ne_bytes())
-                .push_constants_offset(68, &(1.0 / image_info.height as f32).to_ne_bytes())
-                .push_constants_offset(80, &color_to_unorm(color.solid()))
-                .push_constants_offset(96, &color_to_unorm(color.outline()))
+                .push_constants(&push_constants)
                 .bind_vertex_buffer(vertex_buf)
                 .draw(vertex_count, 1, 0, 0);
         });
     }
+
+    /// Returns the push constants of the font shaders, where `distance_range` is the scaled range
+    /// of a signed-distance-field font.
+    fn push_constants(
+        transform: Mat4,
+        framebuffer_extent: [u32; 2],
+        color: &BitmapGlyphColor,
+        distance_range: Option<f32>,
+    ) -> [u8; 120] {
+        let mut data = [0; 120];
+
+        data[0..64].copy_from_slice(cast_slice(&transform.to_cols_array()));
+        data[64..68].copy_from_slice(&(1.0 / framebuffer_extent[0] as f32).to_ne_bytes());
+        data[68..72].copy_from_slice(&(1.0 / framebuffer_extent[1] as f32).to_ne_bytes());
+        data[80..96].copy_from_slice(&color_to_unorm(color.solid()));
+        data[96..112].copy_from_slice(&color_to_unorm(color.outline()));
+
+        if let Some(distance_range) = distance_range {
+            // Smooth the glyph edge across roughly one framebuffer pixel
+            let smoothing = 0.5 / distance_range.max(f32::EPSILON);
+
+            data[112..116].copy_from_slice(&Self::SDF_THRESHOLD.to_ne_bytes());
+            data[116..120].copy_from_slice(&smoothing.to_ne_bytes());
+        }
+
+        data
+    }
 }
 
 pub enum BitmapGlyphColor {
@@ -406,3 +487,104 @@ impl Glyph for BitmapGlyph {
         self.screen_rect.y as _
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the offset, size and name of each push constant declared by GLSL `source`.
+    fn declared_push_constants(source: &str) -> Vec<(usize, usize, &str)> {
+        source
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("layout(offset = "))
+            .map(|line| {
+                let (offset, line) = line.split_once(')').unwrap();
+                let mut words = line.trim().trim_end_matches(';').split_whitespace();
+                let size = match words.next().unwrap() {
+                    "float" => 4,
+                    "vec2" => 8,
+                    "vec4" => 16,
+                    "mat4" => 64,
+                    ty => panic!("unexpected type {ty}"),
+                };
+
+                (offset.parse().unwrap(), size, words.next().unwrap())
+            })
+            .collect()
+    }
+
+    fn f32s(data: &[u8]) -> Vec<f32> {
+        data.chunks_exact(4)
+            .map(|bytes| f32::from_ne_bytes(bytes.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    pub fn sdf_push_constants_match_shaders() {
+        let transform = Mat4::from_scale(vec3(2.0, 3.0, 1.0));
+        let color = BitmapGlyphColor::Solid([255, 0, 51, 255]);
+        let data = BitmapFont::push_constants(transform, [200, 100], &color, Some(4.0));
+        let mut declared = declared_push_constants(include_str!("../res/shader/graphic/font.vert"));
+        declared.extend(declared_push_constants(include_str!(
+            "../res/shader/graphic/font_sdf.frag"
+        )));
+
+        let mut names = declared
+            .iter()
+            .map(|(_, _, name)| *name)
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+
+        assert_eq!(
+            names,
+            [
+                "framebuffer_extent_inverse",
+                "glyph_color",
+                "outline_color",
+                "smoothing",
+                "threshold",
+                "view_proj",
+            ]
+        );
+
+        for (offset, size, name) in declared {
+            let value = f32s(&data[offset..offset + size]);
+            let expected = match name {
+                "view_proj" => transform.to_cols_array().to_vec(),
+                "framebuffer_extent_inverse" => vec![1.0 / 200.0, 1.0 / 100.0],
+                "glyph_color" => vec![1.0, 0.0, 0.2, 1.0],
+                "outline_color" => vec![0.0, 0.0, 0.0, 1.0],
+                "threshold" => vec![BitmapFont::SDF_THRESHOLD],
+                "smoothing" => vec![0.5 / 4.0],
+                _ => unreachable!(),
+            };
+
+            assert_eq!(value, expected, "{name}");
+        }
+    }
+
+    #[test]
+    pub fn push_constants_match_shader() {
+        let data = BitmapFont::push_constants(
+            Mat4::IDENTITY,
+            [1, 1],
+            &BitmapGlyphColor::Outline([0, 255, 0, 255]),
+            None,
+        );
+
+        for (offset, size, name) in
+            declared_push_constants(include_str!("../res/shader/graphic/font.frag"))
+        {
+            let expected = match name {
+                "glyph_color" => vec![0.0, 0.0, 0.0, 1.0],
+                "outline_color" => vec![0.0, 1.0, 0.0, 1.0],
+                _ => unreachable!(),
+            };
+
+            assert_eq!(f32s(&data[offset..offset + size]), expected, "{name}");
+        }
+
+        // Regular fonts leave the signed-distance-field values unset
+        assert_eq!(f32s(&data[112..120]), [0.0, 0.0]);
+    }
+}
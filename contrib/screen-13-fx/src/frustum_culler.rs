@@ -0,0 +1,68 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Culls instance bounding boxes against a view frustum using a compute shader.
+///
+/// Bounding boxes are read as an array of `{ vec4 min; vec4 max; }` (the `w` components are
+/// ignored) and the indices of each visible instance are written, in no particular order, to a
+/// `uint` array. The number of visible instances is written to a separate `uint` buffer which may
+/// be used as the count of an indirect draw.
+#[derive(Debug)]
+pub struct FrustumCuller {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl FrustumCuller {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/frustum_cull.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which tests `instance_count` bounding boxes against the six `planes` of a
+    /// frustum.
+    ///
+    /// Each plane is `[a, b, c, d]` where `(a, b, c)` is a normal pointing into the frustum; a box
+    /// is visible if it is on or in front of every plane. `visible_count` is cleared before the
+    /// pass and `visible` must have room for `instance_count` indices.
+    pub fn cull(
+        &self,
+        graph: &mut RenderGraph,
+        aabbs: impl Into<AnyBufferNode>,
+        instance_count: u32,
+        planes: [[f32; 4]; 6],
+        visible_count: impl Into<AnyBufferNode>,
+        visible: impl Into<AnyBufferNode>,
+    ) {
+        let aabbs = aabbs.into();
+        let visible_count = visible_count.into();
+        let visible = visible.into();
+
+        debug_assert!(graph.node_info(aabbs).size >= instance_count as vk::DeviceSize * 32);
+        debug_assert!(graph.node_info(visible).size >= instance_count as vk::DeviceSize * 4);
+
+        graph.fill_buffer(visible_count, 0);
+
+        if instance_count == 0 {
+            return;
+        }
+
+        graph
+            .begin_pass("frustum cull")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, aabbs)
+            .write_descriptor(1, visible_count)
+            .write_descriptor(2, visible)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&planes))
+                    .push_constants_offset(96, &instance_count.to_ne_bytes())
+                    .dispatch(instance_count.div_ceil(64), 1, 1);
+            });
+    }
+}
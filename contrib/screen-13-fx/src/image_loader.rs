@@ -1,12 +1,21 @@
 use {
-    super::BitmapFont, anyhow::Context, bmfont::BMFont, inline_spirv::include_spirv,
-    screen_13::prelude::*, std::sync::Arc,
+    super::BitmapFont, anyhow::Context, bmfont::BMFont, bytemuck::cast_slice,
+    inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc,
 };
 
+#[cfg(feature = "ttf")]
+use super::ttf::rasterize;
+
 fn align_up_u32(val: u32, atom: u32) -> u32 {
     (val + atom - 1) & !(atom - 1)
 }
 
+/// Returns the row stride, in bytes, of the packed RGB buffers used by the RGB encode shader,
+/// which processes groups of four pixels (twelve bytes) at a time.
+fn rgb_pixel_buf_stride(stride: u32) -> u32 {
+    stride.div_ceil(12) * 12
+}
+
 /// Describes the channels and pixel stride of an image format
 #[derive(Clone, Copy, Debug)]
 pub enum ImageFormat {
@@ -30,8 +39,8 @@ impl ImageFormat {
 #[derive(Debug)]
 pub struct ImageLoader {
     pool: HashPool,
-    _decode_r_rg: Arc<ComputePipeline>,
-    decode_rgb_rgba: Arc<ComputePipeline>,
+    convert: Arc<ComputePipeline>,
+    encode_rgba_rgb: Arc<ComputePipeline>,
     pub device: Arc<Device>,
 }
 
@@ -39,18 +48,18 @@ impl ImageLoader {
     pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
         Ok(Self {
             pool: HashPool::new(device),
-            _decode_r_rg: Arc::new(ComputePipeline::create(
+            convert: Arc::new(ComputePipeline::create(
                 device,
                 ComputePipelineInfo::default(),
                 Shader::new_compute(
-                    include_spirv!("res/shader/compute/decode_bitmap_r_rg.comp", comp).as_slice(),
+                    include_spirv!("res/shader/compute/convert_bitmap.comp", comp).as_slice(),
                 ),
             )?),
-            decode_rgb_rgba: Arc::new(ComputePipeline::create(
+            encode_rgba_rgb: Arc::new(ComputePipeline::create(
                 device,
                 ComputePipelineInfo::default(),
                 Shader::new_compute(
-                    include_spirv!("res/shader/compute/decode_bitmap_rgb_rgba.comp", comp)
+                    include_spirv!("res/shader/compute/encode_bitmap_rgba_rgb.comp", comp)
                         .as_slice(),
                 ),
             )?),
@@ -67,6 +76,13 @@ impl ImageLoader {
         is_temporary: bool,
     ) -> anyhow::Result<Arc<Image>> {
         let format = match format {
+            ImageFormat::R8 if !is_temporary => {
+                if is_srgb {
+                    panic!("Unsupported format: R8_SRGB");
+                } else {
+                    vk::Format::R8_UNORM
+                }
+            }
             ImageFormat::R8 | ImageFormat::R8G8 => {
                 if is_temporary {
                     vk::Format::R8G8_UINT
@@ -105,6 +121,93 @@ impl ImageLoader {
         ))
     }
 
+    /// Converts a bitmap of any format into an `R8G8B8A8` image using a compute shader.
+    ///
+    /// Channels which `format` does not have are set to the matching value of `fill`; for example
+    /// an `R8G8` normal map may be expanded using `[0, 0, 255, 255]`. If `premultiply` is set then
+    /// the color channels are multiplied by alpha. Rows of `pixels` must be tightly packed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn convert_bitmap(
+        &mut self,
+        queue_family_index: usize,
+        queue_index: usize,
+        pixels: &[u8],
+        format: ImageFormat,
+        width: u32,
+        height: u32,
+        is_srgb: bool,
+        fill: [u8; 4],
+        premultiply: bool,
+    ) -> anyhow::Result<Arc<Image>> {
+        info!(
+            "converting {}x{} {:?} bitmap ({} K)",
+            width,
+            height,
+            format,
+            pixels.len() / 1024
+        );
+
+        let channels = format.stride() as u32;
+        let stride = width * channels;
+
+        debug_assert!(
+            pixels.len() >= (stride * height) as usize,
+            "insufficient data"
+        );
+
+        let mut render_graph = RenderGraph::new();
+        let image = render_graph.bind_node(self.create_image(
+            ImageFormat::R8G8B8A8,
+            width,
+            height,
+            is_srgb,
+            false,
+        )?);
+
+        // The shader reads whole words, so the buffer is padded to a multiple of four bytes
+        let mut pixel_buf = self.pool.lease(BufferInfo::host_mem(
+            align_up_u32(pixels.len() as u32, 4) as vk::DeviceSize,
+            vk::BufferUsageFlags::STORAGE_BUFFER,
+        ))?;
+        Buffer::mapped_slice_mut(&mut pixel_buf)[0..pixels.len()].copy_from_slice(pixels);
+
+        let pixel_buf = render_graph.bind_node(pixel_buf);
+        let temp_image = render_graph.bind_node(self.create_image(
+            ImageFormat::R8G8B8A8,
+            width,
+            height,
+            false,
+            true,
+        )?);
+
+        let fill = fill.map(u32::from);
+        let premultiply = premultiply as u32;
+        render_graph
+            .begin_pass("Convert image")
+            .bind_pipeline(&self.convert)
+            .read_descriptor(0, pixel_buf)
+            .write_descriptor(1, temp_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&fill))
+                    .push_constants_offset(
+                        16,
+                        cast_slice(&[width, height, stride, channels, premultiply]),
+                    )
+                    .dispatch(width.div_ceil(8), height.div_ceil(8), 1);
+            })
+            .submit_pass()
+            .copy_image(temp_image, image);
+
+        let image = render_graph.unbind_node(image);
+
+        render_graph
+            .resolve()
+            .submit(&mut self.pool, queue_family_index, queue_index)?;
+
+        Ok(image)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn decode_bitmap(
         &mut self,
@@ -134,96 +237,41 @@ impl ImageLoader {
             warn!("unused data");
         }
 
+        // RGB is expanded to RGBA by the general conversion shader, with opaque alpha
+        if let ImageFormat::R8G8B8 = format {
+            return self.convert_bitmap(
+                queue_family_index,
+                queue_index,
+                pixels,
+                format,
+                width,
+                height,
+                is_srgb,
+                [0, 0, 0, u8::MAX],
+                false,
+            );
+        }
+
         let mut render_graph = RenderGraph::new();
         let image =
             render_graph.bind_node(self.create_image(format, width, height, is_srgb, false)?);
 
-        // Fill the image from the temporary buffer
-        match format {
-            ImageFormat::R8 => {
-                // This format requires a conversion
-                info!("Converting R to RG");
-                todo!()
-            }
-            ImageFormat::R8G8B8 => {
-                // This format requires a conversion
-                //info!("Converting RGB to RGBA");
-
-                let stride = width * format.stride() as u32;
+        // Lease a temporary buffer from the pool
+        let mut pixel_buf = self.pool.lease(BufferInfo::host_mem(
+            pixels.len() as _,
+            vk::BufferUsageFlags::TRANSFER_SRC,
+        ))?;
 
-                //trace!("{bitmap_width}x{bitmap_height} Stride={bitmap_stride}");
-
-                let pixel_buf_stride = align_up_u32(stride, 12);
-                let pixel_buf_len = (pixel_buf_stride * height) as vk::DeviceSize;
-
-                //trace!("pixel_buf_len={pixel_buf_len} pixel_buf_stride={pixel_buf_stride}");
-
-                // Lease a temporary buffer from the cache pool
-                let mut pixel_buf = self.pool.lease(BufferInfo::host_mem(
-                    pixel_buf_len,
-                    vk::BufferUsageFlags::STORAGE_BUFFER,
-                ))?;
-
-                {
-                    let pixel_buf =
-                        &mut Buffer::mapped_slice_mut(&mut pixel_buf)[0..pixel_buf_len as usize];
-
-                    // Fill the temporary buffer with the bitmap pixels - it has a different stride
-                    for y in 0..height {
-                        let src_offset = y * stride;
-                        let src = &pixels[src_offset as usize..(src_offset + stride) as usize];
-
-                        let dst_offset = y * pixel_buf_stride;
-                        let dst =
-                            &mut pixel_buf[dst_offset as usize..(dst_offset + stride) as usize];
-
-                        dst.copy_from_slice(src);
-                    }
-                }
-
-                let pixel_buf = render_graph.bind_node(pixel_buf);
-
-                // We create a temporary storage image because SRGB support isn't wide enough to
-                // have SRGB storage images directly
-                let temp_image =
-                    render_graph.bind_node(self.create_image(format, width, height, false, true)?);
-
-                // Copy host-local data in the buffer to the temporary buffer on the GPU and then
-                // use a compute shader to decode it before copying it over the output image
-
-                let dispatch_x = (width + 3) >> 2;
-                let dispatch_y = height;
-                render_graph
-                    .begin_pass("Decode RGB image")
-                    .bind_pipeline(&self.decode_rgb_rgba)
-                    .read_descriptor(0, pixel_buf)
-                    .write_descriptor(1, temp_image)
-                    .record_compute(move |compute, _| {
-                        compute
-                            .push_constants(&(pixel_buf_stride >> 2).to_ne_bytes())
-                            .dispatch(dispatch_x, dispatch_y, 1);
-                    })
-                    .submit_pass()
-                    .copy_image(temp_image, image);
-            }
-            ImageFormat::R8G8 | ImageFormat::R8G8B8A8 => {
-                // Lease a temporary buffer from the pool
-                let mut pixel_buf = self.pool.lease(BufferInfo::host_mem(
-                    pixels.len() as _,
-                    vk::BufferUsageFlags::TRANSFER_SRC,
-                ))?;
-
-                {
-                    // Fill the temporary buffer with the bitmap pixels
-                    let pixel_buf = &mut Buffer::mapped_slice_mut(&mut pixel_buf)[0..pixels.len()];
-                    pixel_buf.copy_from_slice(pixels);
-                }
-
-                let pixel_buf = render_graph.bind_node(pixel_buf);
-                render_graph.copy_buffer_to_image(pixel_buf, image);
-            }
+        {
+            // Fill the temporary buffer with the bitmap pixels
+            let pixel_buf = &mut Buffer::mapped_slice_mut(&mut pixel_buf)[0..pixels.len()];
+            pixel_buf.copy_from_slice(pixels);
         }
 
+        // Fill the image from the temporary buffer
+        let pixel_buf = render_graph.bind_node(pixel_buf);
+        render_graph.copy_buffer_to_image(pixel_buf, image);
+
         let image = render_graph.unbind_node(image);
 
         render_graph
@@ -273,6 +321,88 @@ impl ImageLoader {
         )
     }
 
+    /// Reads an `R8G8B8A8` image back into tightly-packed `R8G8B8` pixels, discarding alpha.
+    ///
+    /// This is the inverse of decoding an [`ImageFormat::R8G8B8`] bitmap and blocks until the
+    /// device has finished. The image must have been created with `TRANSFER_SRC` usage.
+    pub fn encode_rgb(
+        &mut self,
+        queue_family_index: usize,
+        queue_index: usize,
+        image: &Arc<Image>,
+    ) -> anyhow::Result<Vec<u8>> {
+        let ImageInfo {
+            fmt,
+            height,
+            usage,
+            width,
+            ..
+        } = image.info;
+
+        info!("encoding {}x{} {:?} image", width, height, fmt);
+
+        debug_assert!(
+            matches!(
+                fmt,
+                vk::Format::R8G8B8A8_SRGB | vk::Format::R8G8B8A8_UINT | vk::Format::R8G8B8A8_UNORM
+            ),
+            "unsupported format"
+        );
+        debug_assert!(usage.contains(vk::ImageUsageFlags::TRANSFER_SRC));
+
+        let stride = width * ImageFormat::R8G8B8.stride() as u32;
+        let pixel_buf_stride = rgb_pixel_buf_stride(stride);
+        let pixel_buf_len = (pixel_buf_stride * height) as vk::DeviceSize;
+
+        let mut render_graph = RenderGraph::new();
+        let image = render_graph.bind_node(Arc::clone(image));
+
+        // We copy into a temporary storage image for the same reason decoding does: SRGB storage
+        // images are not widely supported
+        let temp_image = render_graph.bind_node(self.create_image(
+            ImageFormat::R8G8B8A8,
+            width,
+            height,
+            false,
+            true,
+        )?);
+        let pixel_buf = render_graph.bind_node(self.pool.lease(BufferInfo::host_mem(
+            pixel_buf_len,
+            vk::BufferUsageFlags::STORAGE_BUFFER,
+        ))?);
+
+        let dispatch_x = (width + 3) >> 2;
+        let dispatch_y = height;
+        render_graph
+            .copy_image(image, temp_image)
+            .begin_pass("Encode RGB image")
+            .bind_pipeline(&self.encode_rgba_rgb)
+            .read_descriptor(0, temp_image)
+            .write_descriptor(1, pixel_buf)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&(pixel_buf_stride >> 2).to_ne_bytes())
+                    .dispatch(dispatch_x, dispatch_y, 1);
+            });
+
+        let pixel_buf = render_graph.unbind_node(pixel_buf);
+
+        render_graph
+            .resolve()
+            .submit(&mut self.pool, queue_family_index, queue_index)?
+            .wait_until_executed()?;
+
+        // Remove the padding at the end of each row
+        let pixel_buf = Buffer::mapped_slice(&pixel_buf);
+        let mut pixels = Vec::with_capacity((stride * height) as usize);
+        for y in 0..height {
+            let offset = (y * pixel_buf_stride) as usize;
+            pixels.extend_from_slice(&pixel_buf[offset..offset + stride as usize]);
+        }
+
+        Ok(pixels)
+    }
+
     pub fn load_bitmap_font<'a>(
         &mut self,
         queue_family_index: usize,
@@ -296,4 +426,53 @@ impl ImageLoader {
 
         BitmapFont::new(&self.device, font, pages)
     }
+
+    /// Rasterizes the characters of `charset` from TrueType or OpenType font data into a new
+    /// bitmap font.
+    ///
+    /// Characters which the font does not contain are rendered as an empty box.
+    #[cfg(feature = "ttf")]
+    pub fn load_ttf_font(
+        &mut self,
+        queue_family_index: usize,
+        queue_index: usize,
+        bytes: &[u8],
+        size_px: f32,
+        charset: &str,
+    ) -> anyhow::Result<BitmapFont> {
+        let atlas = rasterize(bytes, size_px, charset)?;
+        let page = self.decode_linear(
+            queue_family_index,
+            queue_index,
+            &atlas.pixels,
+            ImageFormat::R8G8,
+            atlas.width,
+            atlas.height,
+        )?;
+
+        BitmapFont::new(&self.device, atlas.font, [page])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn rgb_pixel_buf_stride_fits_dispatch() {
+        // Each RGB encode invocation writes three words for four pixels of a row
+        for width in 1..=64 {
+            let stride = width * ImageFormat::R8G8B8.stride() as u32;
+            let pixel_buf_stride = rgb_pixel_buf_stride(stride);
+            let dispatch_x = (width + 3) >> 2;
+
+            assert_eq!(pixel_buf_stride % 12, 0);
+            assert!(pixel_buf_stride >= stride);
+            assert!(pixel_buf_stride >= dispatch_x * 12);
+        }
+
+        // A 5x2 image has 15 byte rows which are padded to two groups of four pixels
+        assert_eq!(rgb_pixel_buf_stride(5 * 3), 24);
+        assert_eq!(rgb_pixel_buf_stride(4 * 3), 12);
+    }
 }
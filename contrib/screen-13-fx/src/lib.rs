@@ -1,18 +1,30 @@
 pub mod prelude {
     pub use super::{
-        BitmapFont, BitmapGlyphColor, ComputePresenter, GraphicPresenter, ImageFormat, ImageLoader,
-        Transition, TransitionPipeline,
+        BitmapFont, BitmapGlyphColor, ComputePresenter, FrustumCuller, GraphicPresenter,
+        ImageFormat, ImageLoader, MipGenerator, PrefixSum, ToneMapOperator, ToneMapper, Transition,
+        TransitionPipeline,
     };
 }
 
 mod bitmap_font;
+mod frustum_culler;
 mod image_loader;
+mod mip_generator;
+mod prefix_sum;
 mod presenter;
+mod tone_mapper;
 mod transition;
 
+#[cfg(feature = "ttf")]
+mod ttf;
+
 pub use self::{
     bitmap_font::{BitmapFont, BitmapGlyphColor},
+    frustum_culler::FrustumCuller,
     image_loader::{ImageFormat, ImageLoader},
+    mip_generator::MipGenerator,
+    prefix_sum::PrefixSum,
     presenter::{ComputePresenter, GraphicPresenter},
+    tone_mapper::{ToneMapOperator, ToneMapper},
     transition::{Transition, TransitionPipeline},
 };
@@ -0,0 +1,59 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Fills image mip chains using a compute shader and a 2x2 box filter.
+///
+/// Unlike blitting, this does not require a graphics queue. Images must use the
+/// `R8G8B8A8_UNORM` format and have been created with `STORAGE` usage.
+#[derive(Debug)]
+pub struct MipGenerator {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl MipGenerator {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/generate_mip.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records one pass per mip level which downsamples mip `N` into mip `N + 1`, starting from
+    /// the base mip level of `image`.
+    pub fn generate_mips(&self, graph: &mut RenderGraph, image: impl Into<AnyImageNode>) {
+        let image = image.into();
+        let image_info = graph.node_info(image);
+
+        debug_assert_eq!(image_info.fmt, vk::Format::R8G8B8A8_UNORM);
+        debug_assert!(image_info.usage.contains(vk::ImageUsageFlags::STORAGE));
+
+        for mip_level in 1..image_info.mip_level_count {
+            let src_width = (image_info.width >> (mip_level - 1)).max(1);
+            let src_height = (image_info.height >> (mip_level - 1)).max(1);
+            let dst_width = (src_width >> 1).max(1);
+            let dst_height = (src_height >> 1).max(1);
+
+            let mut src_view_info = image_info.default_view_info();
+            src_view_info.base_mip_level = mip_level - 1;
+            src_view_info.mip_level_count = Some(1);
+
+            let mut dst_view_info = src_view_info;
+            dst_view_info.base_mip_level = mip_level;
+
+            graph
+                .begin_pass("generate mip")
+                .bind_pipeline(&self.pipeline)
+                .read_descriptor_as(0, image, src_view_info)
+                .write_descriptor_as(1, image, dst_view_info)
+                .record_compute(move |compute, _| {
+                    compute
+                        .push_constants(cast_slice(&[src_width, src_height]))
+                        .dispatch(dst_width.div_ceil(8), dst_height.div_ceil(8), 1);
+                });
+        }
+    }
+}
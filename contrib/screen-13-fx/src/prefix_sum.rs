@@ -0,0 +1,102 @@
+use {inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Number of elements scanned by each workgroup; must match the shaders.
+const GROUP_SIZE: u32 = 256;
+
+/// Computes exclusive prefix sums (scans) of `u32` buffers using compute shaders.
+///
+/// Buffers of any length are supported: each block of 256 elements is scanned independently and
+/// the block totals are then scanned, recursively, and added back to each block. Buffers must have
+/// been created with `STORAGE_BUFFER` usage.
+#[derive(Debug)]
+pub struct PrefixSum {
+    add: Arc<ComputePipeline>,
+    pool: HashPool,
+    scan: Arc<ComputePipeline>,
+}
+
+impl PrefixSum {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            add: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/prefix_sum_add.comp", comp).as_slice(),
+                ),
+            )?),
+            pool: HashPool::new(device),
+            scan: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/prefix_sum_scan.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records passes which replace the first `len` values of `buffer` with their exclusive
+    /// prefix sum, such that `[1, 1, 1, 1]` becomes `[0, 1, 2, 3]`.
+    pub fn exclusive_scan(
+        &mut self,
+        graph: &mut RenderGraph,
+        buffer: impl Into<AnyBufferNode>,
+        len: u32,
+    ) -> Result<(), DriverError> {
+        let buffer = buffer.into();
+
+        debug_assert!(graph.node_info(buffer).size >= len as vk::DeviceSize * 4);
+
+        if len == 0 {
+            return Ok(());
+        }
+
+        // Scan each level within its blocks, storing the block totals as the next level
+        let mut levels = vec![(buffer, len)];
+        loop {
+            let (buffer, len) = *levels.last().unwrap();
+            let block_count = len.div_ceil(GROUP_SIZE);
+            let block_sums = graph.bind_node(self.pool.lease(BufferInfo::device_mem(
+                block_count as vk::DeviceSize * 4,
+                vk::BufferUsageFlags::STORAGE_BUFFER,
+            ))?);
+
+            graph
+                .begin_pass("prefix sum scan")
+                .bind_pipeline(&self.scan)
+                .write_descriptor(0, buffer)
+                .write_descriptor(1, block_sums)
+                .record_compute(move |compute, _| {
+                    compute
+                        .push_constants(&len.to_ne_bytes())
+                        .dispatch(block_count, 1, 1);
+                });
+
+            if block_count == 1 {
+                break;
+            }
+
+            levels.push((block_sums.into(), block_count));
+        }
+
+        // Add the scanned totals of each block back onto the level below, starting at the top
+        for level in levels.windows(2).rev() {
+            let (buffer, len) = level[0];
+            let (block_sums, block_count) = level[1];
+
+            graph
+                .begin_pass("prefix sum add")
+                .bind_pipeline(&self.add)
+                .write_descriptor(0, buffer)
+                .read_descriptor(1, block_sums)
+                .record_compute(move |compute, _| {
+                    compute
+                        .push_constants(&len.to_ne_bytes())
+                        .dispatch(block_count, 1, 1);
+                });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use {inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Curves which map high dynamic range color values into the displayable `0..=1` range.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ToneMapOperator {
+    /// Filmic curve which approximates the ACES reference rendering transform.
+    #[default]
+    Aces,
+
+    /// Simple curve, `color / (1 + color)`, which never fully saturates.
+    Reinhard,
+}
+
+/// Converts HDR images to LDR images using a compute shader.
+///
+/// The HDR image must use the `R16G16B16A16_SFLOAT` format and the LDR image must use the
+/// `R8G8B8A8_UNORM` format; both must have been created with `STORAGE` usage. Output colors are
+/// sRGB-encoded.
+#[derive(Debug)]
+pub struct ToneMapper {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl ToneMapper {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/tone_map.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which scales `hdr_image` by `exposure`, applies `operator` and writes the
+    /// result to `ldr_image`.
+    pub fn tone_map(
+        &self,
+        graph: &mut RenderGraph,
+        hdr_image: impl Into<AnyImageNode>,
+        ldr_image: impl Into<AnyImageNode>,
+        operator: ToneMapOperator,
+        exposure: f32,
+    ) {
+        let hdr_image = hdr_image.into();
+        let ldr_image = ldr_image.into();
+        let hdr_info = graph.node_info(hdr_image);
+        let ldr_info = graph.node_info(ldr_image);
+
+        debug_assert_eq!(hdr_info.fmt, vk::Format::R16G16B16A16_SFLOAT);
+        debug_assert_eq!(ldr_info.fmt, vk::Format::R8G8B8A8_UNORM);
+        debug_assert!(hdr_info.usage.contains(vk::ImageUsageFlags::STORAGE));
+        debug_assert!(ldr_info.usage.contains(vk::ImageUsageFlags::STORAGE));
+        debug_assert_eq!(hdr_info.width, ldr_info.width);
+        debug_assert_eq!(hdr_info.height, ldr_info.height);
+
+        let operator = match operator {
+            ToneMapOperator::Aces => 0u32,
+            ToneMapOperator::Reinhard => 1,
+        };
+
+        graph
+            .begin_pass("tone map")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, hdr_image)
+            .write_descriptor(1, ldr_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&exposure.to_ne_bytes())
+                    .push_constants_offset(4, &operator.to_ne_bytes())
+                    .dispatch(ldr_info.width.div_ceil(8), ldr_info.height.div_ceil(8), 1);
+            });
+    }
+}
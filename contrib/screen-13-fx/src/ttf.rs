@@ -0,0 +1,251 @@
+use {
+    anyhow::anyhow,
+    bmfont::{BMFont, OrdinateOrientation},
+    fontdue::{Font, FontSettings, Metrics},
+    screen_13::prelude::warn,
+    std::{collections::BTreeSet, fmt::Write, io::Cursor},
+};
+
+/// Atlas pages are this wide unless a single glyph requires more space.
+const ATLAS_WIDTH: u32 = 256;
+
+/// Empty pixels between each glyph on the atlas page.
+const PADDING: u32 = 1;
+
+/// A single-page glyph atlas rasterized from a TrueType or OpenType font.
+///
+/// Pixels are stored as `R8G8` where the red channel is glyph coverage and the green channel is
+/// the (unused) outline coverage, which is the layout the bitmap font shaders expect.
+pub(crate) struct TtfAtlas {
+    pub font: BMFont,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+    pub width: u32,
+}
+
+struct Glyph {
+    char: char,
+    coverage: Vec<u8>,
+    metrics: Metrics,
+    x: u32,
+    y: u32,
+}
+
+/// Returns coverage for a "tofu" box which is drawn in place of glyphs the font does not have.
+fn tofu(size_px: f32, ascent: f32) -> (Metrics, Vec<u8>) {
+    let width = (size_px * 0.5).ceil().max(3.0) as usize;
+    let height = ascent.ceil().max(3.0) as usize;
+    let mut coverage = vec![0; width * height];
+
+    for y in 0..height {
+        for x in 0..width {
+            if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+                coverage[y * width + x] = u8::MAX;
+            }
+        }
+    }
+
+    let metrics = Metrics {
+        width,
+        height,
+        advance_width: (width + 2) as f32,
+        ..Default::default()
+    };
+
+    (metrics, coverage)
+}
+
+pub(crate) fn rasterize(bytes: &[u8], size_px: f32, charset: &str) -> anyhow::Result<TtfAtlas> {
+    let (fnt, width, height, pixels) = rasterize_fnt(bytes, size_px, charset)?;
+    let font = BMFont::new(Cursor::new(fnt), OrdinateOrientation::TopToBottom)
+        .map_err(|err| anyhow!("Unable to create bitmap font: {err:?}"))?;
+
+    Ok(TtfAtlas {
+        font,
+        height,
+        pixels,
+        width,
+    })
+}
+
+/// Returns the text of the `.fnt` file and the width, height and pixels of the atlas page.
+fn rasterize_fnt(
+    bytes: &[u8],
+    size_px: f32,
+    charset: &str,
+) -> anyhow::Result<(String, u32, u32, Vec<u8>)> {
+    let font = Font::from_bytes(bytes, FontSettings::default())
+        .map_err(|err| anyhow!("Unable to parse font: {err}"))?;
+    let (ascent, line_height) = font
+        .horizontal_line_metrics(size_px)
+        .map(|metrics| (metrics.ascent, metrics.new_line_size))
+        .unwrap_or((size_px, size_px));
+    let base = ascent.ceil() as i32;
+
+    let mut glyphs = charset
+        .chars()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|char| {
+            let (metrics, coverage) = if font.lookup_glyph_index(char) == 0 {
+                warn!("Missing glyph: {char:?}");
+
+                tofu(size_px, ascent)
+            } else {
+                font.rasterize(char, size_px)
+            };
+
+            Glyph {
+                char,
+                coverage,
+                metrics,
+                x: 0,
+                y: 0,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Pack the glyphs into rows ("shelves") from left to right
+    let width = glyphs
+        .iter()
+        .map(|glyph| glyph.metrics.width as u32 + PADDING)
+        .max()
+        .unwrap_or_default()
+        .max(ATLAS_WIDTH);
+    let (mut x, mut y, mut row_height) = (0, 0, 0);
+    for glyph in &mut glyphs {
+        let (glyph_width, glyph_height) = (
+            glyph.metrics.width as u32 + PADDING,
+            glyph.metrics.height as u32 + PADDING,
+        );
+
+        if x + glyph_width > width {
+            x = 0;
+            y += row_height;
+            row_height = 0;
+        }
+
+        glyph.x = x;
+        glyph.y = y;
+
+        x += glyph_width;
+        row_height = row_height.max(glyph_height);
+    }
+
+    let height = (y + row_height).max(1);
+    let mut pixels = vec![0; (width * height * 2) as usize];
+    let mut fnt = String::new();
+
+    writeln!(
+        fnt,
+        "info face=\"ttf\" size={} bold=0 italic=0 charset=\"\" unicode=1 stretchH=100 smooth=1 \
+        aa=1 padding=0,0,0,0 spacing={PADDING},{PADDING}",
+        size_px.round() as u32,
+    )?;
+    writeln!(
+        fnt,
+        "common lineHeight={} base={base} scaleW={width} scaleH={height} pages=1 packed=0",
+        line_height.ceil() as u32,
+    )?;
+    writeln!(fnt, "page id=0 file=\"ttf_0.png\"")?;
+    writeln!(fnt, "chars count={}", glyphs.len())?;
+
+    for glyph in &glyphs {
+        let glyph_width = glyph.metrics.width;
+        for (row, coverage) in glyph
+            .coverage
+            .chunks_exact(glyph_width.max(1))
+            .take(glyph.metrics.height)
+            .enumerate()
+        {
+            let offset = (((glyph.y as usize + row) * width as usize) + glyph.x as usize) * 2;
+            for (idx, coverage) in coverage.iter().enumerate() {
+                pixels[offset + idx * 2] = *coverage;
+            }
+        }
+
+        writeln!(
+            fnt,
+            "char id={} x={} y={} width={} height={} xoffset={} yoffset={} xadvance={} page=0 \
+            chnl=15",
+            glyph.char as u32,
+            glyph.x,
+            glyph.y,
+            glyph.metrics.width,
+            glyph.metrics.height,
+            glyph.metrics.xmin,
+            base - (glyph.metrics.ymin + glyph.metrics.height as i32),
+            glyph.metrics.advance_width.round() as i32,
+        )?;
+    }
+
+    writeln!(fnt, "kernings count=0")?;
+
+    Ok((fnt, width, height, pixels))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::collections::HashMap};
+
+    const ROBOTO: &[u8] =
+        include_bytes!("../../screen-13-imgui/res/font/roboto/roboto-regular.ttf");
+
+    /// Returns the `key=value` pairs of the first line of `fnt` which starts with `tag`.
+    fn fnt_line<'a>(fnt: &'a str, tag: &str, id: Option<char>) -> HashMap<&'a str, i32> {
+        fnt.lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>())
+            .filter(|words| words.first() == Some(&tag))
+            .map(|words| {
+                words
+                    .into_iter()
+                    .filter_map(|word| word.split_once('='))
+                    .filter_map(|(key, value)| value.parse().ok().map(|value| (key, value)))
+                    .collect::<HashMap<_, _>>()
+            })
+            .find(|values| id.map_or(true, |id| values.get("id") == Some(&(id as i32))))
+            .unwrap()
+    }
+
+    #[test]
+    pub fn rasterize_glyphs() {
+        let missing = '\u{e000}';
+        let charset = format!("AB{missing}");
+        let (fnt, width, height, pixels) = rasterize_fnt(ROBOTO, 32.0, &charset).unwrap();
+
+        assert!(width > 0);
+        assert!(height > 0);
+        assert_eq!(pixels.len(), (width * height * 2) as usize);
+        assert!(pixels.iter().any(|&pixel| pixel != 0));
+
+        let common = fnt_line(&fnt, "common", None);
+        let base = common["base"];
+
+        assert!(base > 0);
+        assert_eq!(common["scaleW"], width as i32);
+        assert_eq!(common["scaleH"], height as i32);
+        assert_eq!(fnt_line(&fnt, "chars", None)["count"], 3);
+
+        for char in ['A', 'B'] {
+            let glyph = fnt_line(&fnt, "char", Some(char));
+
+            assert!(glyph["width"] > 0);
+            assert!(glyph["height"] > 0);
+            assert!(glyph["x"] + glyph["width"] <= width as i32);
+            assert!(glyph["y"] + glyph["height"] <= height as i32);
+
+            // Capitals sit on the baseline and do not rise above the ascent
+            assert!(glyph["yoffset"] >= 0);
+            assert!((glyph["yoffset"] + glyph["height"] - base).abs() <= 1);
+            assert!(glyph["xadvance"] > 0);
+        }
+
+        let tofu = fnt_line(&fnt, "char", Some(missing));
+
+        assert!(tofu["width"] > 0);
+        assert!(tofu["height"] > 0);
+        assert!(tofu["xadvance"] > tofu["width"]);
+
+        assert!(rasterize(ROBOTO, 32.0, &charset).is_ok());
+    }
+}
@@ -0,0 +1,435 @@
+use {bytemuck::cast_slice, half::f16, screen_13::prelude::*, screen_13_fx::*, std::sync::Arc};
+
+// Kind of an example, kind of a test: runs the compute utilities of screen-13-fx on small, known
+// inputs and checks the results after reading them back on the CPU.
+//
+// Each check records its work into a render graph, waits for it to execute, and then asserts on
+// the contents of a host-visible buffer - see cpu_readback.rs for the basics of reading back data.
+fn main() -> anyhow::Result<()> {
+    pretty_env_logger::init();
+
+    let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    let mut pool = HashPool::new(&device);
+
+    check_generate_mips(&device, &mut pool)?;
+    check_frustum_cull(&device, &mut pool)?;
+    check_decode_r8(&device, &mut pool)?;
+    check_convert_rg8(&device, &mut pool)?;
+    check_decode_rgb8(&device, &mut pool)?;
+    check_tone_map(&device, &mut pool)?;
+    check_prefix_sum(&device, &mut pool)?;
+
+    println!("OK");
+
+    Ok(())
+}
+
+/// A 4x4 image reduces to a 2x2 mip where each pixel is the average of a 2x2 block.
+fn check_generate_mips(device: &Arc<Device>, pool: &mut HashPool) -> anyhow::Result<()> {
+    // The red channel of the 4x4 image looks like this (green is the inverse, 255 - red):
+    //     0   16   32   48
+    //    64   80   96  112
+    //   128  144  160  176
+    //   192  208  224  240
+    let pixels = (0..16u8)
+        .flat_map(|idx| [idx * 16, 255 - idx * 16, 0, 255])
+        .collect::<Vec<_>>();
+
+    let mut render_graph = RenderGraph::new();
+    let image = render_graph.bind_node(Image::create(
+        device,
+        ImageInfo::image_2d(
+            4,
+            4,
+            vk::Format::R8G8B8A8_UNORM,
+            vk::ImageUsageFlags::STORAGE
+                | vk::ImageUsageFlags::TRANSFER_DST
+                | vk::ImageUsageFlags::TRANSFER_SRC,
+        )
+        .to_builder()
+        .mip_level_count(2)
+        .build(),
+    )?);
+    let pixel_buf = render_graph.bind_node(Buffer::create_from_slice(
+        device,
+        vk::BufferUsageFlags::TRANSFER_SRC,
+        &pixels,
+    )?);
+    let result_buf = render_graph.bind_node(Buffer::create(
+        device,
+        BufferInfo::host_mem(2 * 2 * 4, vk::BufferUsageFlags::TRANSFER_DST),
+    )?);
+
+    render_graph.copy_buffer_to_image(pixel_buf, image);
+    MipGenerator::new(device)?.generate_mips(&mut render_graph, image);
+    render_graph.copy_image_to_buffer_region(
+        image,
+        result_buf,
+        vk::BufferImageCopy {
+            buffer_offset: 0,
+            buffer_row_length: 2,
+            buffer_image_height: 2,
+            image_subresource: vk::ImageSubresourceLayers {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                mip_level: 1,
+                base_array_layer: 0,
+                layer_count: 1,
+            },
+            image_offset: Default::default(),
+            image_extent: vk::Extent3D {
+                width: 2,
+                height: 2,
+                depth: 1,
+            },
+        },
+    );
+
+    let result_buf = render_graph.unbind_node(result_buf);
+
+    render_graph
+        .resolve()
+        .submit(pool, 0, 0)?
+        .wait_until_executed()?;
+
+    // The red channel of mip 1 should look like this:
+    //    40   72
+    //   168  200
+    let result = Buffer::mapped_slice(&result_buf);
+
+    println!("generate_mips: {result:?}");
+
+    for (pixel, red) in result.chunks_exact(4).zip([40u8, 72, 168, 200]) {
+        assert!(pixel[0].abs_diff(red) <= 1);
+        assert!(pixel[1].abs_diff(255 - red) <= 1);
+        assert_eq!(pixel[2], 0);
+        assert_eq!(pixel[3], 255);
+    }
+
+    Ok(())
+}
+
+/// Boxes inside or straddling the frustum are visible; boxes entirely outside of it are not.
+fn check_frustum_cull(device: &Arc<Device>, pool: &mut HashPool) -> anyhow::Result<()> {
+    // The frustum is the box from -1 to 1 on each axis, with normals pointing inwards
+    let planes = [
+        [1.0, 0.0, 0.0, 1.0],
+        [-1.0, 0.0, 0.0, 1.0],
+        [0.0, 1.0, 0.0, 1.0],
+        [0.0, -1.0, 0.0, 1.0],
+        [0.0, 0.0, 1.0, 1.0],
+        [0.0, 0.0, -1.0, 1.0],
+    ];
+
+    // Each instance is the min and max corner of a box (w is unused)
+    let aabbs: [[f32; 8]; 4] = [
+        // Inside
+        [0.0, 0.0, 0.0, 0.0, 0.5, 0.5, 0.5, 0.0],
+        // Straddles the x = 1 plane
+        [0.5, -0.5, -0.5, 0.0, 2.0, 0.5, 0.5, 0.0],
+        // Outside on +x
+        [3.0, 0.0, 0.0, 0.0, 4.0, 0.5, 0.5, 0.0],
+        // Outside on -y
+        [0.0, -5.0, 0.0, 0.0, 0.5, -3.0, 0.5, 0.0],
+    ];
+
+    let mut render_graph = RenderGraph::new();
+    let aabb_buf = render_graph.bind_node(Buffer::create_from_slice(
+        device,
+        vk::BufferUsageFlags::STORAGE_BUFFER,
+        cast_slice(&aabbs),
+    )?);
+    let visible_count_buf = render_graph.bind_node(Buffer::create(
+        device,
+        BufferInfo::host_mem(
+            4,
+            vk::BufferUsageFlags::STORAGE_BUFFER | vk::BufferUsageFlags::TRANSFER_DST,
+        ),
+    )?);
+    let visible_buf = render_graph.bind_node(Buffer::create(
+        device,
+        BufferInfo::host_mem(
+            4 * aabbs.len() as vk::DeviceSize,
+            vk::BufferUsageFlags::STORAGE_BUFFER,
+        ),
+    )?);
+
+    FrustumCuller::new(device)?.cull(
+        &mut render_graph,
+        aabb_buf,
+        aabbs.len() as _,
+        planes,
+        visible_count_buf,
+        visible_buf,
+    );
+
+    let visible_count_buf = render_graph.unbind_node(visible_count_buf);
+    let visible_buf = render_graph.unbind_node(visible_buf);
+
+    render_graph
+        .resolve()
+        .submit(pool, 0, 0)?
+        .wait_until_executed()?;
+
+    let visible_count: &[u32] = cast_slice(Buffer::mapped_slice(&visible_count_buf));
+    let visible: &[u32] = cast_slice(Buffer::mapped_slice(&visible_buf));
+    let mut visible = visible[..visible_count[0] as usize].to_vec();
+    visible.sort_unstable();
+
+    println!("frustum_cull: {visible:?}");
+
+    assert_eq!(visible, [0, 1]);
+
+    Ok(())
+}
+
+/// Single channel bitmaps, including those with rows only one byte long, are decoded unchanged.
+fn check_decode_r8(device: &Arc<Device>, pool: &mut HashPool) -> anyhow::Result<()> {
+    let mut image_loader = ImageLoader::new(device)?;
+
+    for (width, height) in [(1, 3), (3, 3), (5, 2)] {
+        let pixels = (0..width * height)
+            .map(|idx| (idx * 17 + 1) as u8)
+            .collect::<Vec<_>>();
+        let image = image_loader.decode_linear(0, 0, &pixels, ImageFormat::R8, width, height)?;
+
+        assert_eq!(image.info.fmt, vk::Format::R8_UNORM);
+
+        let mut render_graph = RenderGraph::new();
+        let image = render_graph.bind_node(image);
+        let result_buf = render_graph.bind_node(Buffer::create(
+            device,
+            BufferInfo::host_mem(pixels.len() as _, vk::BufferUsageFlags::TRANSFER_DST),
+        )?);
+
+        render_graph.copy_image_to_buffer(image, result_buf);
+
+        let result_buf = render_graph.unbind_node(result_buf);
+
+        render_graph
+            .resolve()
+            .submit(pool, 0, 0)?
+            .wait_until_executed()?;
+
+        let result = Buffer::mapped_slice(&result_buf);
+
+        println!("decode_r8 {width}x{height}: {result:?}");
+
+        assert_eq!(result, pixels);
+    }
+
+    Ok(())
+}
+
+/// Two channel bitmaps convert to RGBA, with blue and alpha set to the fill constants.
+fn check_convert_rg8(device: &Arc<Device>, pool: &mut HashPool) -> anyhow::Result<()> {
+    let (width, height) = (3, 2);
+    let pixels = (0..width * height * 2)
+        .map(|idx| (idx * 19 + 3) as u8)
+        .collect::<Vec<_>>();
+    let fill = [0, 0, 200, 100];
+    let image = ImageLoader::new(device)?.convert_bitmap(
+        0,
+        0,
+        &pixels,
+        ImageFormat::R8G8,
+        width,
+        height,
+        false,
+        fill,
+        false,
+    )?;
+
+    assert_eq!(image.info.fmt, vk::Format::R8G8B8A8_UNORM);
+
+    let result_buf = read_image(device, pool, image, width * height * 4)?;
+    let result = Buffer::mapped_slice(&result_buf);
+
+    println!("convert_rg8: {result:?}");
+
+    for (pixel, rg) in result.chunks_exact(4).zip(pixels.chunks_exact(2)) {
+        assert_eq!(pixel[0..2], *rg);
+        assert_eq!(pixel[2], fill[2]);
+        assert_eq!(pixel[3], fill[3]);
+    }
+
+    Ok(())
+}
+
+/// Three channel bitmaps, including those with rows which are not a multiple of four pixels, decode
+/// to opaque RGBA.
+fn check_decode_rgb8(device: &Arc<Device>, pool: &mut HashPool) -> anyhow::Result<()> {
+    let mut image_loader = ImageLoader::new(device)?;
+
+    for (width, height) in [(1, 2), (4, 2), (5, 3)] {
+        let pixels = (0..width * height * 3)
+            .map(|idx| (idx * 13 + 5) as u8)
+            .collect::<Vec<_>>();
+        let image =
+            image_loader.decode_linear(0, 0, &pixels, ImageFormat::R8G8B8, width, height)?;
+
+        assert_eq!(image.info.fmt, vk::Format::R8G8B8A8_UNORM);
+
+        let result_buf = read_image(device, pool, image, width * height * 4)?;
+        let result = Buffer::mapped_slice(&result_buf);
+
+        println!("decode_rgb8 {width}x{height}: {result:?}");
+
+        for (pixel, rgb) in result.chunks_exact(4).zip(pixels.chunks_exact(3)) {
+            assert_eq!(pixel[0..3], *rgb);
+            assert_eq!(pixel[3], u8::MAX);
+        }
+    }
+
+    Ok(())
+}
+
+/// Copies the contents of an image into a new host-visible buffer.
+fn read_image(
+    device: &Arc<Device>,
+    pool: &mut HashPool,
+    image: Arc<Image>,
+    len: u32,
+) -> anyhow::Result<Arc<Buffer>> {
+    let mut render_graph = RenderGraph::new();
+    let image = render_graph.bind_node(image);
+    let result_buf = render_graph.bind_node(Buffer::create(
+        device,
+        BufferInfo::host_mem(len as _, vk::BufferUsageFlags::TRANSFER_DST),
+    )?);
+
+    render_graph.copy_image_to_buffer(image, result_buf);
+
+    let result_buf = render_graph.unbind_node(result_buf);
+
+    render_graph
+        .resolve()
+        .submit(pool, 0, 0)?
+        .wait_until_executed()?;
+
+    Ok(result_buf)
+}
+
+/// Known HDR values map to the expected sRGB-encoded values of each tone mapping curve.
+fn check_tone_map(device: &Arc<Device>, pool: &mut HashPool) -> anyhow::Result<()> {
+    let hdr_pixels = [[1.0, 3.0, 0.0, 1.0], [0.5, 1.0, 0.0, 1.0]]
+        .into_iter()
+        .flatten()
+        .map(f16::from_f32)
+        .collect::<Vec<_>>();
+    let tone_mapper = ToneMapper::new(device)?;
+
+    // Reinhard maps 1.0 to 0.5 (188 in sRGB) and 3.0 to 0.75 (225 in sRGB); ACES maps 1.0 to
+    // 0.804 (232 in sRGB)
+    for (operator, expected) in [
+        (
+            ToneMapOperator::Reinhard,
+            [[188, 225, 0, 255], [156, 188, 0, 255]],
+        ),
+        (
+            ToneMapOperator::Aces,
+            [[232, 250, 0, 255], [206, 232, 0, 255]],
+        ),
+    ] {
+        let mut render_graph = RenderGraph::new();
+        let hdr_buf = render_graph.bind_node(Buffer::create_from_slice(
+            device,
+            vk::BufferUsageFlags::TRANSFER_SRC,
+            cast_slice(&hdr_pixels),
+        )?);
+        let hdr_image = render_graph.bind_node(Image::create(
+            device,
+            ImageInfo::image_2d(
+                2,
+                1,
+                vk::Format::R16G16B16A16_SFLOAT,
+                vk::ImageUsageFlags::STORAGE | vk::ImageUsageFlags::TRANSFER_DST,
+            ),
+        )?);
+        let ldr_image = render_graph.bind_node(Image::create(
+            device,
+            ImageInfo::image_2d(
+                2,
+                1,
+                vk::Format::R8G8B8A8_UNORM,
+                vk::ImageUsageFlags::STORAGE | vk::ImageUsageFlags::TRANSFER_SRC,
+            ),
+        )?);
+        let result_buf = render_graph.bind_node(Buffer::create(
+            device,
+            BufferInfo::host_mem(2 * 4, vk::BufferUsageFlags::TRANSFER_DST),
+        )?);
+
+        render_graph.copy_buffer_to_image(hdr_buf, hdr_image);
+        tone_mapper.tone_map(&mut render_graph, hdr_image, ldr_image, operator, 1.0);
+        render_graph.copy_image_to_buffer(ldr_image, result_buf);
+
+        let result_buf = render_graph.unbind_node(result_buf);
+
+        render_graph
+            .resolve()
+            .submit(pool, 0, 0)?
+            .wait_until_executed()?;
+
+        let result = Buffer::mapped_slice(&result_buf);
+
+        println!("tone_map {operator:?}: {result:?}");
+
+        for (actual, expected) in result.iter().zip(expected.into_iter().flatten()) {
+            assert!(actual.abs_diff(expected) <= 1);
+        }
+    }
+
+    Ok(())
+}
+
+/// Exclusive prefix sums match a CPU scan, including lengths which need more than one level of
+/// block sums.
+fn check_prefix_sum(device: &Arc<Device>, pool: &mut HashPool) -> anyhow::Result<()> {
+    let mut prefix_sum = PrefixSum::new(device)?;
+
+    // 1,000 values need two levels and 70,000 values need three (256 * 256 = 65,536)
+    for len in [4u32, 256, 1_000, 70_000] {
+        let values = if len == 4 {
+            vec![1; 4]
+        } else {
+            (0..len).map(|idx| idx % 7).collect::<Vec<_>>()
+        };
+
+        let mut render_graph = RenderGraph::new();
+        let buffer = render_graph.bind_node(Buffer::create_from_slice(
+            device,
+            vk::BufferUsageFlags::STORAGE_BUFFER,
+            cast_slice(&values),
+        )?);
+
+        prefix_sum.exclusive_scan(&mut render_graph, buffer, len)?;
+
+        let buffer = render_graph.unbind_node(buffer);
+
+        render_graph
+            .resolve()
+            .submit(pool, 0, 0)?
+            .wait_until_executed()?;
+
+        let result: &[u32] = cast_slice(Buffer::mapped_slice(&buffer));
+        let expected = values
+            .iter()
+            .scan(0, |sum, value| {
+                let prefix = *sum;
+                *sum += value;
+
+                Some(prefix)
+            })
+            .collect::<Vec<_>>();
+
+        println!("prefix_sum {len}: {:?}", &result[..result.len().min(8)]);
+
+        if len == 4 {
+            assert_eq!(result, [0, 1, 2, 3]);
+        }
+
+        assert_eq!(result, expected);
+    }
+
+    Ok(())
+}
@@ -212,7 +212,10 @@ impl From<()> for DisplayError {
 
 impl From<DriverError> for DisplayError {
     fn from(err: DriverError) -> Self {
-        Self::Driver(err)
+        match err {
+            DriverError::DeviceLost => Self::DeviceLost,
+            err => Self::Driver(err),
+        }
     }
 }
 
@@ -240,3 +243,20 @@ impl<T> ResolverPool for T where
         + Pool<CommandBufferInfo, CommandBuffer>
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn display_error_from_device_lost() {
+        assert!(matches!(
+            DisplayError::from(DriverError::DeviceLost),
+            DisplayError::DeviceLost
+        ));
+        assert!(matches!(
+            DisplayError::from(DriverError::OutOfMemory),
+            DisplayError::Driver(DriverError::OutOfMemory)
+        ));
+    }
+}
@@ -12,7 +12,8 @@ use {
     log::warn,
     std::{
         fmt::{Debug, Formatter},
-        mem::ManuallyDrop,
+        marker::PhantomData,
+        mem::{size_of, ManuallyDrop},
         ops::{Deref, Range},
         sync::{
             atomic::{AtomicU8, Ordering},
@@ -135,7 +136,7 @@ impl Buffer {
                     allocation_scheme: AllocationScheme::GpuAllocatorManaged,
                 })
                 .map_err(|err| {
-                    warn!("{err}");
+                    warn!("unable to allocate {} byte buffer: {err}", info.size);
 
                     DriverError::Unsupported
                 })
@@ -391,6 +392,154 @@ impl Buffer {
 
         &mut this.allocation.mapped_slice_mut().unwrap()[0..this.info.size as usize]
     }
+
+    /// Returns a pointer to the mapped memory of this buffer.
+    ///
+    /// Callers must ensure writes do not overlap any range the device may be accessing.
+    pub(crate) fn mapped_ptr(this: &Self) -> *mut u8 {
+        debug_assert!(
+            this.info.mappable,
+            "Buffer is not mappable - create using mappable flag"
+        );
+
+        this.allocation.mapped_ptr().unwrap().as_ptr() as *mut u8
+    }
+
+    /// Reads a value of type `T` from a mappable buffer starting at `offset`.
+    ///
+    /// `T` should be a `#[repr(C)]` type whose layout matches the `std140` or `std430` block it
+    /// represents; the size of `T` must be a non-zero multiple of four bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer was not created with the `mappable` flag set to `true` or if the value
+    /// does not fit within the buffer.
+    ///
+    /// # Safety
+    ///
+    /// Every bit pattern of the bytes read must be a valid value of `T`: types such as `bool`,
+    /// `char`, enums, references and other types with invalid bit patterns must not be used.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::buffer::{Buffer, BufferInfo};
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::new())?);
+    /// # let info = BufferInfo::host_mem(16, vk::BufferUsageFlags::UNIFORM_BUFFER);
+    /// # let my_buf = Buffer::create(&device, info)?;
+    /// let color: [f32; 4] = unsafe { Buffer::read_uniform(&my_buf, 0) };
+    /// # Ok(()) }
+    /// ```
+    #[profiling::function]
+    pub unsafe fn read_uniform<T>(this: &Self, offset: vk::DeviceSize) -> T
+    where
+        T: Copy + 'static,
+    {
+        let data = &Self::mapped_slice(this)[offset as usize..offset as usize + size_of::<T>()];
+
+        uniform_from_bytes(data)
+    }
+
+    /// Updates a mappable buffer starting at `offset` with the bytes of `value`.
+    ///
+    /// `T` should be a `#[repr(C)]` type whose layout matches the `std140` or `std430` block it
+    /// represents; the size of `T` must be a non-zero multiple of four bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer was not created with the `mappable` flag set to `true` or if the value
+    /// does not fit within the buffer.
+    ///
+    /// # Safety
+    ///
+    /// `T` must not contain padding bytes or other uninitialized memory; add explicit padding
+    /// fields where the block layout requires them.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::buffer::{Buffer, BufferInfo};
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::new())?);
+    /// # let info = BufferInfo::host_mem(80, vk::BufferUsageFlags::UNIFORM_BUFFER);
+    /// # let mut my_buf = Buffer::create(&device, info)?;
+    /// #[repr(C)]
+    /// #[derive(Clone, Copy)]
+    /// struct Camera {
+    ///     view_proj: [[f32; 4]; 4],
+    ///     position: [f32; 4],
+    /// }
+    ///
+    /// let camera = Camera {
+    ///     view_proj: [[0.0; 4]; 4],
+    ///     position: [0.0, 1.0, 2.0, 1.0],
+    /// };
+    /// unsafe {
+    ///     Buffer::write_uniform(&mut my_buf, 0, &camera);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    #[profiling::function]
+    pub unsafe fn write_uniform<T>(this: &mut Self, offset: vk::DeviceSize, value: &T)
+    where
+        T: Copy + 'static,
+    {
+        Self::copy_from_slice(this, offset, uniform_as_bytes(value));
+    }
+}
+
+/// Returns the bytes of `value`.
+///
+/// # Safety
+///
+/// `T` must not contain padding bytes.
+unsafe fn uniform_as_bytes<T>(value: &T) -> &[u8]
+where
+    T: Copy + 'static,
+{
+    let () = UniformLayout::<T>::VALID;
+
+    std::slice::from_raw_parts((value as *const T).cast::<u8>(), size_of::<T>())
+}
+
+/// Reads a value of type `T` from the start of `data`, which need not be aligned.
+///
+/// # Safety
+///
+/// Every bit pattern must be a valid value of `T`.
+unsafe fn uniform_from_bytes<T>(data: &[u8]) -> T
+where
+    T: Copy + 'static,
+{
+    let () = UniformLayout::<T>::VALID;
+
+    assert!(data.len() >= size_of::<T>());
+
+    data.as_ptr().cast::<T>().read_unaligned()
+}
+
+/// Compile-time check of the size of types used with [`Buffer::read_uniform`] and
+/// [`Buffer::write_uniform`].
+struct UniformLayout<T>(PhantomData<T>);
+
+impl<T> UniformLayout<T> {
+    const VALID: () = assert!(
+        size_of::<T>() != 0 && size_of::<T>() % 4 == 0,
+        "Uniform size must be a non-zero multiple of four bytes"
+    );
 }
 
 impl Debug for Buffer {
@@ -638,6 +787,31 @@ mod tests {
         assert_eq!(info.alignment, 1);
     }
 
+    #[test]
+    pub fn uniform_round_trip() {
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Light {
+            position: [f32; 3],
+            intensity: f32,
+            color: [u32; 4],
+        }
+
+        let light = Light {
+            position: [1.0, -2.0, 3.5],
+            intensity: 0.25,
+            color: [1, 2, 3, u32::MAX],
+        };
+
+        // Offset the copy by one byte so the read is unaligned
+        let mut data = vec![0u8; size_of::<Light>() + 1];
+        data[1..].copy_from_slice(unsafe { uniform_as_bytes(&light) });
+
+        assert_eq!(&data[1..5], &1.0f32.to_ne_bytes());
+        assert_eq!(&data[29..33], &u32::MAX.to_ne_bytes());
+        assert_eq!(unsafe { uniform_from_bytes::<Light>(&data[1..]) }, light);
+    }
+
     #[test]
     pub fn buffer_info_builder() {
         let info = Info::device_mem(0, vk::BufferUsageFlags::empty());
@@ -1,8 +1,16 @@
 use {
-    super::{device::Device, DriverError},
-    ash::vk,
+    super::{device::Device, DriverError, Instance},
+    ash::{extensions::ext, vk},
     log::{error, trace, warn},
-    std::{fmt::Debug, ops::Deref, sync::Arc, thread::panicking},
+    std::{
+        ffi::CString,
+        fmt::{Debug, Formatter},
+        ops::Deref,
+        slice::from_ref,
+        sync::Arc,
+        thread::panicking,
+        time::Duration,
+    },
 };
 
 /// Represents a Vulkan command buffer to which some work has been submitted.
@@ -86,9 +94,7 @@ impl CommandBuffer {
         match res {
             Ok(status) => Ok(status),
             Err(err) if err == vk::Result::ERROR_DEVICE_LOST => {
-                error!("Device lost");
-
-                Err(DriverError::InvalidData)
+                Err(Device::mark_lost(&self.device))
             }
             Err(err) => {
                 // VK_SUCCESS and VK_NOT_READY handled by get_fence_status in ash
@@ -108,11 +114,38 @@ impl CommandBuffer {
     /// Stalls by blocking the current thread until the GPU has executed the previous submission to
     /// this command buffer.
     ///
-    /// See [`Self::has_executed`] to check without blocking.
+    /// See [`Self::has_executed`] to check without blocking or
+    /// [`Self::wait_until_executed_timeout`] to limit how long to block.
     #[profiling::function]
     pub fn wait_until_executed(&self) -> Result<(), DriverError> {
         Device::wait_for_fence(&self.device, &self.fence)
     }
+
+    /// Stalls by blocking the current thread until the GPU has executed the previous submission to
+    /// this command buffer or `timeout` elapses.
+    ///
+    /// Returns `true` if execution has completed and `false` if the wait timed out, which may
+    /// indicate that the device is not responding.
+    #[profiling::function]
+    pub fn wait_until_executed_timeout(&self, timeout: Duration) -> Result<bool, DriverError> {
+        let timeout = timeout.as_nanos().min(u64::MAX as _) as u64;
+
+        match unsafe {
+            self.device
+                .wait_for_fences(from_ref(&self.fence), true, timeout)
+        } {
+            Ok(_) => Ok(true),
+            Err(err) if err == vk::Result::TIMEOUT => Ok(false),
+            Err(err) if err == vk::Result::ERROR_DEVICE_LOST => {
+                Err(Device::mark_lost(&self.device))
+            }
+            Err(err) => {
+                warn!("{err}");
+
+                Err(DriverError::OutOfMemory)
+            }
+        }
+    }
 }
 
 impl Deref for CommandBuffer {
@@ -126,8 +159,6 @@ impl Deref for CommandBuffer {
 impl Drop for CommandBuffer {
     #[profiling::function]
     fn drop(&mut self) {
-        use std::slice::from_ref;
-
         if panicking() {
             return;
         }
@@ -145,6 +176,107 @@ impl Drop for CommandBuffer {
     }
 }
 
+#[cfg(test)]
+thread_local! {
+    /// The number of debug label regions which have begun and ended on this thread.
+    static DEBUG_LABEL_COUNTS: std::cell::Cell<(usize, usize)> = std::cell::Cell::new((0, 0));
+}
+
+/// A region of a command buffer which is grouped under a name by debugging tools such as
+/// RenderDoc and Nsight.
+///
+/// The region ends when this guard is dropped. Labels are only recorded when the device was
+/// created with debugging enabled; otherwise this type does nothing.
+pub struct DebugLabel<'a> {
+    cmd_buf: vk::CommandBuffer,
+    debug_utils: Option<&'a ext::DebugUtils>,
+}
+
+impl<'a> DebugLabel<'a> {
+    /// Begins a debug label region on `cmd_buf`, which must be in the recording state.
+    ///
+    /// `color` is an optional RGBA color for the region; use all zeroes for no color.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use screen_13::driver::{DebugLabel, DriverError};
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::graph::RenderGraph;
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::new())?);
+    /// # let mut render_graph = RenderGraph::new();
+    /// render_graph
+    ///     .begin_pass("my pass")
+    ///     .record_cmd_buf(|device, cmd_buf, _| {
+    ///         let red = [1.0, 0.0, 0.0, 1.0];
+    ///         let _label = DebugLabel::begin(device, cmd_buf, "my commands", red);
+    ///
+    ///         // (Record commands here)
+    ///     });
+    /// # Ok(()) }
+    /// ```
+    #[profiling::function]
+    pub fn begin(
+        device: &'a Device,
+        cmd_buf: vk::CommandBuffer,
+        name: &str,
+        color: [f32; 4],
+    ) -> Self {
+        let debug_utils = Instance::debug_utils(Device::instance(device));
+
+        if let Some(debug_utils) = debug_utils {
+            let name = CString::new(name).unwrap_or_default();
+            let label = vk::DebugUtilsLabelEXT::builder()
+                .label_name(&name)
+                .color(color);
+
+            unsafe {
+                debug_utils.cmd_begin_debug_utils_label(cmd_buf, &label);
+            }
+
+            #[cfg(test)]
+            DEBUG_LABEL_COUNTS.with(|counts| {
+                let (begun, ended) = counts.get();
+                counts.set((begun + 1, ended));
+            });
+        }
+
+        Self {
+            cmd_buf,
+            debug_utils,
+        }
+    }
+}
+
+impl Debug for DebugLabel<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DebugLabel")
+            .field("cmd_buf", &self.cmd_buf)
+            .field("enabled", &self.debug_utils.is_some())
+            .finish()
+    }
+}
+
+impl Drop for DebugLabel<'_> {
+    fn drop(&mut self) {
+        if let Some(debug_utils) = self.debug_utils {
+            unsafe {
+                debug_utils.cmd_end_debug_utils_label(self.cmd_buf);
+            }
+
+            #[cfg(test)]
+            DEBUG_LABEL_COUNTS.with(|counts| {
+                let (begun, ended) = counts.get();
+                counts.set((begun, ended + 1));
+            });
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub struct CommandBufferInfo {
     pub queue_family_index: u32,
@@ -155,3 +287,113 @@ impl CommandBufferInfo {
         Self { queue_family_index }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            driver::{
+                buffer::{Buffer, BufferInfo},
+                device::DeviceInfoBuilder,
+                TimelineSemaphore,
+            },
+            graph::RenderGraph,
+            pool::hash::HashPool,
+        },
+    };
+
+    #[test]
+    pub fn debug_labels_are_balanced() {
+        // Labels are only recorded by devices which have debugging enabled
+        let device = match Device::create_headless(DeviceInfoBuilder::default().debug(true)) {
+            Ok(device) => Arc::new(device),
+            Err(err) => {
+                eprintln!("skipping test: unable to create debug device: {err:?}");
+
+                return;
+            }
+        };
+        let mut pool = HashPool::new(&device);
+        let mut render_graph = RenderGraph::new();
+        let buffer = render_graph.bind_node(
+            Buffer::create(
+                &device,
+                BufferInfo::device_mem(16, vk::BufferUsageFlags::TRANSFER_DST),
+            )
+            .unwrap(),
+        );
+
+        render_graph
+            .fill_buffer(buffer, 1)
+            .fill_buffer(buffer, 2)
+            .begin_pass("nested labels")
+            .record_cmd_buf(|device, cmd_buf, _| {
+                let _outer = DebugLabel::begin(device, cmd_buf, "outer", [0.0; 4]);
+                let _inner = DebugLabel::begin(device, cmd_buf, "inner", [1.0; 4]);
+            });
+
+        let (begun_before, ended_before) = DEBUG_LABEL_COUNTS.with(|counts| counts.get());
+
+        render_graph
+            .resolve()
+            .submit(&mut pool, 0, 0)
+            .unwrap()
+            .wait_until_executed()
+            .unwrap();
+
+        let (begun, ended) = DEBUG_LABEL_COUNTS.with(|counts| counts.get());
+
+        // Every pass is labelled, along with the two regions recorded by the last, and every
+        // region which was begun has been ended
+        assert!(begun - begun_before >= 3);
+        assert_eq!(begun - begun_before, ended - ended_before);
+    }
+
+    #[test]
+    pub fn wait_until_executed_timeout() {
+        let Some(device) = Device::create_test() else {
+            return;
+        };
+        let Ok(semaphore) = TimelineSemaphore::create(&device, 0) else {
+            eprintln!("skipping test: timeline semaphores are not supported");
+
+            return;
+        };
+        let cmd_buf = CommandBuffer::create(&device, CommandBufferInfo::new(0)).unwrap();
+
+        // The submission cannot execute until the host signals the semaphore
+        unsafe {
+            device
+                .begin_command_buffer(*cmd_buf, &vk::CommandBufferBeginInfo::default())
+                .unwrap();
+            device.end_command_buffer(*cmd_buf).unwrap();
+            device.reset_fences(from_ref(&cmd_buf.fence)).unwrap();
+
+            let wait_value = 1;
+            let mut timeline_info = vk::TimelineSemaphoreSubmitInfo::builder()
+                .wait_semaphore_values(from_ref(&wait_value));
+            let submit_info = vk::SubmitInfo::builder()
+                .command_buffers(from_ref(&cmd_buf.cmd_buf))
+                .wait_semaphores(from_ref(&*semaphore))
+                .wait_dst_stage_mask(from_ref(&vk::PipelineStageFlags::ALL_COMMANDS))
+                .push_next(&mut timeline_info);
+
+            device
+                .queue_submit(device.queues[0][0], from_ref(&submit_info), cmd_buf.fence)
+                .unwrap();
+        }
+
+        assert!(!cmd_buf
+            .wait_until_executed_timeout(Duration::from_millis(10))
+            .unwrap());
+        assert!(!cmd_buf.has_executed().unwrap());
+
+        TimelineSemaphore::signal(&semaphore, 1).unwrap();
+
+        assert!(cmd_buf
+            .wait_until_executed_timeout(Duration::from_secs(10))
+            .unwrap());
+        assert!(cmd_buf.has_executed().unwrap());
+    }
+}
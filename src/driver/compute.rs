@@ -4,7 +4,7 @@ use {
     super::{
         device::Device,
         shader::{DescriptorBindingMap, PipelineDescriptorInfo, Shader},
-        DriverError,
+        validate_push_constant_ranges, DriverError,
     },
     ash::vk,
     derive_builder::{Builder, UninitializedFieldError},
@@ -94,7 +94,7 @@ impl ComputePipeline {
         let descriptor_set_layouts = descriptor_info
             .layouts
             .values()
-            .map(|descriptor_set_layout| **descriptor_set_layout)
+            .map(|descriptor_set_layout| ***descriptor_set_layout)
             .collect::<Box<[_]>>();
 
         unsafe {
@@ -131,6 +131,15 @@ impl ComputePipeline {
 
             let push_constants = shader.push_constant_range();
             if let Some(push_constants) = &push_constants {
+                validate_push_constant_ranges(
+                    from_ref(push_constants),
+                    device
+                        .physical_device
+                        .properties_v1_0
+                        .limits
+                        .max_push_constants_size,
+                )?;
+
                 layout_info = layout_info.push_constant_ranges(from_ref(push_constants));
             }
 
@@ -118,7 +118,11 @@ impl DescriptorPool {
         let descriptor_pool = unsafe {
             device.create_descriptor_pool(
                 &vk::DescriptorPoolCreateInfo::builder()
-                    .flags(vk::DescriptorPoolCreateFlags::FREE_DESCRIPTOR_SET)
+                    .flags(if info.free_individual {
+                        vk::DescriptorPoolCreateFlags::FREE_DESCRIPTOR_SET
+                    } else {
+                        vk::DescriptorPoolCreateFlags::empty()
+                    })
                     .max_sets(info.max_sets)
                     .pool_sizes(&pool_sizes[0..pool_size_count]),
                 None,
@@ -180,9 +184,26 @@ impl DescriptorPool {
                     descriptor_pool: this.descriptor_pool,
                     descriptor_set,
                     device: Arc::clone(&this.device),
+                    free_on_drop: this.info.free_individual,
                 })
         })
     }
+
+    /// Returns all descriptor sets allocated from this pool back to the pool.
+    ///
+    /// Descriptor sets allocated from this pool must not be used after calling this function.
+    #[profiling::function]
+    pub fn reset(this: &Self) -> Result<(), DriverError> {
+        unsafe {
+            this.device
+                .reset_descriptor_pool(this.descriptor_pool, vk::DescriptorPoolResetFlags::empty())
+        }
+        .map_err(|err| {
+            warn!("{err}");
+
+            DriverError::OutOfMemory
+        })
+    }
 }
 
 impl Deref for DescriptorPool {
@@ -207,10 +228,16 @@ impl Drop for DescriptorPool {
     }
 }
 
-#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct DescriptorPoolInfo {
     pub acceleration_structure_count: u32,
     pub combined_image_sampler_count: u32,
+
+    /// When `true` descriptor sets are freed individually when dropped, otherwise the whole pool
+    /// must be reset using [`DescriptorPool::reset`] before it is reused. The default value is
+    /// `true`.
+    pub free_individual: bool,
+
     pub input_attachment_count: u32,
     pub max_sets: u32,
     pub sampled_image_count: u32,
@@ -223,6 +250,26 @@ pub struct DescriptorPoolInfo {
     pub uniform_texel_buffer_count: u32,
 }
 
+impl Default for DescriptorPoolInfo {
+    fn default() -> Self {
+        Self {
+            acceleration_structure_count: 0,
+            combined_image_sampler_count: 0,
+            free_individual: true,
+            input_attachment_count: 0,
+            max_sets: 0,
+            sampled_image_count: 0,
+            storage_buffer_count: 0,
+            storage_buffer_dynamic_count: 0,
+            storage_image_count: 0,
+            storage_texel_buffer_count: 0,
+            uniform_buffer_count: 0,
+            uniform_buffer_dynamic_count: 0,
+            uniform_texel_buffer_count: 0,
+        }
+    }
+}
+
 impl DescriptorPoolInfo {
     pub fn is_empty(&self) -> bool {
         self.acceleration_structure_count
@@ -245,6 +292,7 @@ pub struct DescriptorSet {
     descriptor_pool: vk::DescriptorPool,
     descriptor_set: vk::DescriptorSet,
     device: Arc<Device>,
+    free_on_drop: bool,
 }
 
 impl Deref for DescriptorSet {
@@ -260,7 +308,7 @@ impl Drop for DescriptorSet {
     fn drop(&mut self) {
         use std::slice::from_ref;
 
-        if panicking() {
+        if panicking() || !self.free_on_drop {
             return;
         }
 
@@ -271,3 +319,17 @@ impl Drop for DescriptorSet {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn descriptor_pool_info_default() {
+        let info = DescriptorPoolInfo::default();
+
+        assert!(info.free_individual);
+        assert_eq!(info.max_sets, 0);
+        assert!(info.is_empty());
+    }
+}
@@ -1,10 +1,29 @@
 use {
     super::{device::Device, DriverError},
     ash::vk,
-    log::warn,
-    std::{ops::Deref, sync::Arc, thread::panicking},
+    log::{trace, warn},
+    std::{
+        ops::Deref,
+        slice::from_raw_parts,
+        sync::{Arc, Weak},
+        thread::panicking,
+    },
 };
 
+/// Uniquely identifies the bindings of a descriptor set layout.
+///
+/// Each binding is stored as binding index, descriptor type, descriptor count, stage flags and any
+/// immutable samplers.
+pub(crate) type DescriptorSetLayoutKey = Box<
+    [(
+        u32,
+        vk::DescriptorType,
+        u32,
+        vk::ShaderStageFlags,
+        Box<[vk::Sampler]>,
+    )],
+>;
+
 #[derive(Debug)]
 pub struct DescriptorSetLayout {
     device: Arc<Device>,
@@ -33,6 +52,71 @@ impl DescriptorSetLayout {
             descriptor_set_layout,
         })
     }
+
+    /// Returns a descriptor set layout which is shared with any other pipelines which have the
+    /// same bindings, creating a new layout if needed.
+    ///
+    /// Layouts are cached on the device only while they are in use.
+    #[profiling::function]
+    pub fn create_cached(
+        device: &Arc<Device>,
+        info: &vk::DescriptorSetLayoutCreateInfo,
+    ) -> Result<Arc<Self>, DriverError> {
+        let key = unsafe { Self::cache_key(info) };
+
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut cache = device.descriptor_set_layout_cache.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut cache = cache.unwrap();
+
+        if let Some(descriptor_set_layout) = cache.get(&key).and_then(Weak::upgrade) {
+            trace!("create_cached: reusing {:?}", *descriptor_set_layout);
+
+            return Ok(descriptor_set_layout);
+        }
+
+        let descriptor_set_layout = Arc::new(Self::create(device, info)?);
+
+        // Discard entries for layouts which are no longer used by any pipeline
+        cache.retain(|_, descriptor_set_layout| descriptor_set_layout.strong_count() > 0);
+        cache.insert(key, Arc::downgrade(&descriptor_set_layout));
+
+        Ok(descriptor_set_layout)
+    }
+
+    /// Returns the key which identifies the bindings of `info` in the device cache.
+    ///
+    /// # Safety
+    ///
+    /// The binding and immutable sampler pointers of `info` must be valid.
+    unsafe fn cache_key(info: &vk::DescriptorSetLayoutCreateInfo) -> DescriptorSetLayoutKey {
+        let bindings = if info.binding_count > 0 {
+            from_raw_parts(info.p_bindings, info.binding_count as _)
+        } else {
+            &[]
+        };
+
+        bindings
+            .iter()
+            .map(|binding| {
+                let immutable_samplers = if binding.p_immutable_samplers.is_null() {
+                    Box::default()
+                } else {
+                    from_raw_parts(binding.p_immutable_samplers, binding.descriptor_count as _)
+                        .into()
+                };
+
+                (
+                    binding.binding,
+                    binding.descriptor_type,
+                    binding.descriptor_count,
+                    binding.stage_flags,
+                    immutable_samplers,
+                )
+            })
+            .collect()
+    }
 }
 
 impl Deref for DescriptorSetLayout {
@@ -56,3 +140,84 @@ impl Drop for DescriptorSetLayout {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, ash::vk::Handle};
+
+    fn binding(
+        binding: u32,
+        descriptor_type: vk::DescriptorType,
+        immutable_samplers: &[vk::Sampler],
+    ) -> vk::DescriptorSetLayoutBinding {
+        let mut binding = vk::DescriptorSetLayoutBinding::builder()
+            .binding(binding)
+            .descriptor_type(descriptor_type)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::COMPUTE);
+
+        if !immutable_samplers.is_empty() {
+            binding = binding.immutable_samplers(immutable_samplers);
+        }
+
+        binding.build()
+    }
+
+    fn key(bindings: &[vk::DescriptorSetLayoutBinding]) -> DescriptorSetLayoutKey {
+        let info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(bindings);
+
+        unsafe { DescriptorSetLayout::cache_key(&info) }
+    }
+
+    #[test]
+    pub fn descriptor_set_layout_cache_key() {
+        let sampler = [vk::Sampler::from_raw(1)];
+        let other_sampler = [vk::Sampler::from_raw(2)];
+
+        let lhs = [
+            binding(0, vk::DescriptorType::STORAGE_BUFFER, &[]),
+            binding(1, vk::DescriptorType::COMBINED_IMAGE_SAMPLER, &sampler),
+        ];
+        let rhs = lhs;
+
+        assert_eq!(key(&lhs), key(&rhs));
+        assert!(key(&[]).is_empty());
+
+        // Any difference in the bindings is a different layout
+        assert_ne!(
+            key(&lhs),
+            key(&[binding(0, vk::DescriptorType::UNIFORM_BUFFER, &[]), lhs[1],])
+        );
+        assert_ne!(
+            key(&lhs),
+            key(&[
+                lhs[0],
+                binding(
+                    1,
+                    vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+                    &other_sampler
+                ),
+            ])
+        );
+        assert_ne!(key(&lhs), key(&lhs[..1]));
+    }
+
+    #[test]
+    pub fn descriptor_set_layout_create_cached() {
+        let Some(device) = Device::create_test() else {
+            return;
+        };
+
+        let bindings = [binding(0, vk::DescriptorType::STORAGE_BUFFER, &[])];
+        let info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(&bindings);
+        let other_bindings = [binding(0, vk::DescriptorType::UNIFORM_BUFFER, &[])];
+        let other_info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(&other_bindings);
+
+        let layout = DescriptorSetLayout::create_cached(&device, &info).unwrap();
+        let same_layout = DescriptorSetLayout::create_cached(&device, &info).unwrap();
+        let other_layout = DescriptorSetLayout::create_cached(&device, &other_info).unwrap();
+
+        assert!(Arc::ptr_eq(&layout, &same_layout));
+        assert!(!Arc::ptr_eq(&layout, &other_layout));
+    }
+}
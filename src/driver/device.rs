@@ -1,7 +1,12 @@
 //! Logical device resource types
 
 use {
-    super::{physical_device::PhysicalDevice, DriverError, Instance},
+    super::{
+        descriptor_set_layout::DescriptorSetLayoutKey,
+        physical_device::PhysicalDevice,
+        shader::{Sampler, SamplerInfo},
+        DescriptorSetLayout, DriverError, Instance,
+    },
     ash::{extensions::khr, vk},
     ash_window::enumerate_required_extensions,
     derive_builder::{Builder, UninitializedFieldError},
@@ -13,11 +18,16 @@ use {
     raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle},
     std::{
         cmp::Ordering,
+        collections::HashMap,
         ffi::CStr,
         fmt::{Debug, Formatter},
         iter::{empty, repeat},
         mem::{forget, ManuallyDrop},
         ops::Deref,
+        sync::{
+            atomic::{AtomicBool, Ordering as AtomicOrdering},
+            Weak,
+        },
         thread::panicking,
         time::Instant,
     },
@@ -38,11 +48,17 @@ pub struct Device {
 
     pub(super) allocator: ManuallyDrop<Mutex<Allocator>>,
 
+    /// Descriptor set layouts which are currently in use, shared between pipelines.
+    pub(super) descriptor_set_layout_cache:
+        Mutex<HashMap<DescriptorSetLayoutKey, Weak<DescriptorSetLayout>>>,
+
     device: ash::Device,
 
     /// Vulkan instance pointer, which includes useful functions.
     instance: Instance,
 
+    lost: AtomicBool,
+
     /// The physical device, which contains useful data about features, properties, and limits.
     pub physical_device: PhysicalDevice,
 
@@ -51,6 +67,9 @@ pub struct Device {
 
     pub(crate) ray_trace_ext: Option<khr::RayTracingPipeline>,
 
+    /// Samplers which are currently in use, shared between pipelines.
+    pub(super) sampler_cache: Mutex<HashMap<SamplerInfo, Weak<Sampler>>>,
+
     pub(super) surface_ext: Option<khr::Surface>,
     pub(super) swapchain_ext: Option<khr::Swapchain>,
 }
@@ -311,11 +330,14 @@ impl Device {
         Ok(Self {
             accel_struct_ext,
             allocator: ManuallyDrop::new(Mutex::new(allocator)),
+            descriptor_set_layout_cache: Default::default(),
             device,
             instance,
+            lost: AtomicBool::new(false),
             physical_device,
             queues,
             ray_trace_ext,
+            sampler_cache: Default::default(),
             surface_ext,
             swapchain_ext,
         })
@@ -369,6 +391,23 @@ impl Device {
         &this.instance
     }
 
+    /// Returns `true` if any operation on this device has observed `VK_ERROR_DEVICE_LOST`.
+    ///
+    /// A lost device cannot be recovered: the application must drop this device, along with all
+    /// resources and pipelines created from it, and create a new device.
+    pub fn is_lost(this: &Self) -> bool {
+        this.lost.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Marks this device as lost and returns the matching error.
+    pub(crate) fn mark_lost(this: &Self) -> DriverError {
+        if !this.lost.swap(true, AtomicOrdering::Relaxed) {
+            error!("Device lost");
+        }
+
+        DriverError::DeviceLost
+    }
+
     #[profiling::function]
     pub(crate) fn wait_for_fence(this: &Self, fence: &vk::Fence) -> Result<(), DriverError> {
         use std::slice::from_ref;
@@ -382,9 +421,7 @@ impl Device {
             match this.device.wait_for_fences(fences, true, 100) {
                 Ok(_) => return Ok(()),
                 Err(err) if err == vk::Result::ERROR_DEVICE_LOST => {
-                    error!("Device lost");
-
-                    return Err(DriverError::InvalidData);
+                    return Err(Device::mark_lost(this));
                 }
                 Err(err) if err == vk::Result::TIMEOUT => {
                     trace!("waiting...");
@@ -397,9 +434,7 @@ impl Device {
             match this.device.wait_for_fences(fences, true, u64::MAX) {
                 Ok(_) => (),
                 Err(err) if err == vk::Result::ERROR_DEVICE_LOST => {
-                    error!("Device lost");
-
-                    return Err(DriverError::InvalidData);
+                    return Err(Device::mark_lost(this));
                 }
                 _ => return Err(DriverError::OutOfMemory),
             }
@@ -620,6 +655,21 @@ impl DeviceInfoBuilder {
     }
 }
 
+#[cfg(test)]
+impl Device {
+    /// Returns a headless device for tests, or `None` if this machine has no Vulkan device.
+    pub(crate) fn create_test() -> Option<std::sync::Arc<Self>> {
+        match Self::create_headless(DeviceInfo::default()) {
+            Ok(device) => Some(std::sync::Arc::new(device)),
+            Err(err) => {
+                eprintln!("skipping test: unable to create device: {err:?}");
+
+                None
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 struct DeviceInfoBuilderError;
 
@@ -645,4 +695,25 @@ mod tests {
     pub fn device_info_builder() {
         Builder::default().build();
     }
+
+    #[test]
+    pub fn device_mark_lost() {
+        let Some(device) = Device::create_test() else {
+            return;
+        };
+
+        assert!(!Device::is_lost(&device));
+        assert!(matches!(
+            Device::mark_lost(&device),
+            DriverError::DeviceLost
+        ));
+        assert!(Device::is_lost(&device));
+
+        // Marking a lost device again keeps it lost
+        assert!(matches!(
+            Device::mark_lost(&device),
+            DriverError::DeviceLost
+        ));
+        assert!(Device::is_lost(&device));
+    }
 }
@@ -6,7 +6,7 @@ use {
         image::SampleCount,
         merge_push_constant_ranges,
         shader::{DescriptorBindingMap, PipelineDescriptorInfo, Shader, SpecializationInfo},
-        DriverError,
+        validate_push_constant_ranges, DriverError,
     },
     ash::vk,
     derive_builder::{Builder, UninitializedFieldError},
@@ -463,7 +463,7 @@ impl GraphicPipeline {
         let descriptor_sets_layouts = descriptor_info
             .layouts
             .values()
-            .map(|descriptor_set_layout| **descriptor_set_layout)
+            .map(|descriptor_set_layout| ***descriptor_set_layout)
             .collect::<Box<[_]>>();
 
         let push_constants = shaders
@@ -471,6 +471,14 @@ impl GraphicPipeline {
             .map(|shader| shader.push_constant_range())
             .filter_map(|mut push_const| push_const.take())
             .collect::<Vec<_>>();
+        validate_push_constant_ranges(
+            &push_constants,
+            device
+                .physical_device
+                .properties_v1_0
+                .limits
+                .max_push_constants_size,
+        )?;
 
         let input_attachments = {
             let (input, write) = shaders
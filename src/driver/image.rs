@@ -142,7 +142,10 @@ impl Image {
                     allocation_scheme: AllocationScheme::GpuAllocatorManaged,
                 })
                 .map_err(|err| {
-                    warn!("{err}");
+                    warn!(
+                        "unable to allocate {}x{}x{} {:?} image: {err}",
+                        info.width, info.height, info.depth, info.fmt
+                    );
 
                     DriverError::Unsupported
                 })
@@ -228,6 +228,12 @@ impl Instance {
         res
     }
 
+    /// Returns the `VK_EXT_debug_utils` functions if this instance was created with debug layers
+    /// enabled.
+    pub(crate) fn debug_utils(this: &Self) -> Option<&ext::DebugUtils> {
+        this.debug_utils.as_ref()
+    }
+
     /// Returns `true` if this instance was created with debug layers enabled.
     pub fn is_debug(this: &Self) -> bool {
         this.debug_utils.is_some()
@@ -43,9 +43,14 @@ mod cmd_buf;
 mod descriptor_set;
 mod descriptor_set_layout;
 mod instance;
+mod semaphore;
 
 pub use {
-    self::{cmd_buf::CommandBuffer, instance::Instance},
+    self::{
+        cmd_buf::{CommandBuffer, DebugLabel},
+        instance::Instance,
+        semaphore::TimelineSemaphore,
+    },
     ash::{self},
     vk_sync::AccessType,
 };
@@ -73,6 +78,7 @@ use {
         image::SampleCount,
     },
     ash::vk,
+    log::warn,
     std::{
         cmp::Ordering,
         error::Error,
@@ -465,6 +471,29 @@ fn merge_push_constant_ranges(pcr: &[vk::PushConstantRange]) -> Vec<vk::PushCons
     }
 }
 
+/// Checks that every push constant range fits within the `max_push_constants_size` device limit.
+///
+/// Pipeline layout creation with larger ranges is invalid usage, so this is checked up front in
+/// order to return an error instead.
+fn validate_push_constant_ranges(
+    pcr: &[vk::PushConstantRange],
+    max_push_constants_size: u32,
+) -> Result<(), DriverError> {
+    for item in pcr {
+        if item.offset + item.size > max_push_constants_size {
+            warn!(
+                "push constant range {}..{} exceeds device limit of {max_push_constants_size} bytes",
+                item.offset,
+                item.offset + item.size,
+            );
+
+            return Err(DriverError::Unsupported);
+        }
+    }
+
+    Ok(())
+}
+
 pub(super) const fn pipeline_stage_access_flags(
     access_type: AccessType,
 ) -> (vk::PipelineStageFlags, vk::AccessFlags) {
@@ -650,6 +679,12 @@ pub(super) const fn pipeline_stage_access_flags(
 /// help debugging the issue.
 #[derive(Debug)]
 pub enum DriverError {
+    /// The device has been lost and can no longer be used.
+    ///
+    /// This is not recoverable: the device and everything created from it must be dropped and a
+    /// new device created. See [`Device::is_lost`](device::Device::is_lost).
+    DeviceLost,
+
     /// The input data, or referenced data, is not valid for the current state.
     InvalidData,
 
@@ -672,7 +707,10 @@ impl Error for DriverError {}
 
 #[cfg(test)]
 mod tests {
-    use {super::merge_push_constant_ranges, ash::vk};
+    use {
+        super::{merge_push_constant_ranges, validate_push_constant_ranges, DriverError},
+        ash::vk,
+    };
 
     macro_rules! assert_pcr_eq {
         ($lhs: expr, $rhs: expr,) => {
@@ -985,4 +1023,27 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    pub fn push_constant_ranges_exceed_limit() {
+        let pcr = [
+            vk::PushConstantRange {
+                stage_flags: vk::ShaderStageFlags::VERTEX,
+                offset: 0,
+                size: 64,
+            },
+            vk::PushConstantRange {
+                stage_flags: vk::ShaderStageFlags::FRAGMENT,
+                offset: 64,
+                size: 68,
+            },
+        ];
+
+        assert!(validate_push_constant_ranges(&pcr, 256).is_ok());
+        assert!(validate_push_constant_ranges(&pcr, 132).is_ok());
+        assert!(matches!(
+            validate_push_constant_ranges(&pcr, 128),
+            Err(DriverError::Unsupported)
+        ));
+    }
 }
@@ -6,7 +6,7 @@ use {
         merge_push_constant_ranges,
         physical_device::RayTraceProperties,
         shader::{DescriptorBindingMap, PipelineDescriptorInfo, Shader},
-        DriverError,
+        validate_push_constant_ranges, DriverError,
     },
     ash::vk,
     derive_builder::{Builder, UninitializedFieldError},
@@ -127,6 +127,14 @@ impl RayTracePipeline {
             .map(|shader| shader.push_constant_range())
             .filter_map(|mut push_const| push_const.take())
             .collect::<Vec<_>>();
+        validate_push_constant_ranges(
+            &push_constants,
+            device
+                .physical_device
+                .properties_v1_0
+                .limits
+                .max_push_constants_size,
+        )?;
 
         // Use SPIR-V reflection to get the types and counts of all descriptors
         let mut descriptor_bindings = Shader::merge_descriptor_bindings(
@@ -145,7 +153,7 @@ impl RayTracePipeline {
         let descriptor_set_layout_handles = descriptor_info
             .layouts
             .values()
-            .map(|descriptor_set_layout| **descriptor_set_layout)
+            .map(|descriptor_set_layout| ***descriptor_set_layout)
             .collect::<Box<[_]>>();
 
         unsafe {
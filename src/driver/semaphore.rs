@@ -0,0 +1,207 @@
+use {
+    super::{device::Device, DriverError},
+    ash::vk,
+    log::{trace, warn},
+    std::{ops::Deref, slice::from_ref, sync::Arc, thread::panicking, time::Duration},
+};
+
+/// Smart pointer handle to a timeline [semaphore] object.
+///
+/// A timeline semaphore holds a monotonically increasing 64-bit value which may be signalled and
+/// waited upon by both the host and the device. Render graphs may signal a value on submission
+/// using [`Resolver::submit_signal`](crate::graph::Resolver::submit_signal).
+///
+/// Requires [`Vulkan12Features::timeline_semaphore`](super::physical_device::Vulkan12Features);
+/// when unavailable the fence returned by each submission may be used instead.
+///
+/// [semaphore]: https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkSemaphore.html
+#[derive(Debug)]
+pub struct TimelineSemaphore {
+    device: Arc<Device>,
+    semaphore: vk::Semaphore,
+}
+
+impl TimelineSemaphore {
+    /// Creates a new timeline semaphore with the given starting value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DriverError::Unsupported`] if the device does not support timeline semaphores.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use screen_13::driver::{DriverError, TimelineSemaphore};
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::new())?);
+    /// let semaphore = TimelineSemaphore::create(&device, 0)?;
+    ///
+    /// TimelineSemaphore::signal(&semaphore, 1)?;
+    ///
+    /// assert_eq!(TimelineSemaphore::value(&semaphore)?, 1);
+    /// # Ok(()) }
+    /// ```
+    #[profiling::function]
+    pub fn create(device: &Arc<Device>, initial_value: u64) -> Result<Self, DriverError> {
+        trace!("create: {initial_value}");
+
+        if !device.physical_device.features_v1_2.timeline_semaphore {
+            warn!("timeline semaphores are not supported");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        let device = Arc::clone(device);
+        let mut type_info = vk::SemaphoreTypeCreateInfo::builder()
+            .semaphore_type(vk::SemaphoreType::TIMELINE)
+            .initial_value(initial_value);
+        let create_info = vk::SemaphoreCreateInfo::builder().push_next(&mut type_info);
+        let semaphore = unsafe { device.create_semaphore(&create_info, None) }.map_err(|err| {
+            warn!("{err}");
+
+            DriverError::OutOfMemory
+        })?;
+
+        Ok(Self { device, semaphore })
+    }
+
+    /// Sets the value of this semaphore from the host.
+    ///
+    /// `value` must be greater than the current value and any value which has been submitted to be
+    /// signalled by the device.
+    #[profiling::function]
+    pub fn signal(this: &Self, value: u64) -> Result<(), DriverError> {
+        let signal_info = vk::SemaphoreSignalInfo::builder()
+            .semaphore(this.semaphore)
+            .value(value);
+
+        unsafe { this.device.signal_semaphore(&signal_info) }.map_err(|err| {
+            warn!("{err}");
+
+            DriverError::OutOfMemory
+        })
+    }
+
+    /// Returns the current value of this semaphore.
+    #[profiling::function]
+    pub fn value(this: &Self) -> Result<u64, DriverError> {
+        unsafe { this.device.get_semaphore_counter_value(this.semaphore) }.map_err(|err| {
+            if err == vk::Result::ERROR_DEVICE_LOST {
+                Device::mark_lost(&this.device)
+            } else {
+                warn!("{err}");
+
+                DriverError::OutOfMemory
+            }
+        })
+    }
+
+    /// Blocks until the value of this semaphore is at least `value` or `timeout` elapses.
+    ///
+    /// Returns `true` if the value was reached and `false` if the wait timed out.
+    #[profiling::function]
+    pub fn wait(this: &Self, value: u64, timeout: Duration) -> Result<bool, DriverError> {
+        let wait_info = vk::SemaphoreWaitInfo::builder()
+            .semaphores(from_ref(&this.semaphore))
+            .values(from_ref(&value));
+        let timeout = timeout.as_nanos().min(u64::MAX as _) as u64;
+
+        match unsafe { this.device.wait_semaphores(&wait_info, timeout) } {
+            Ok(_) => Ok(true),
+            Err(err) if err == vk::Result::TIMEOUT => Ok(false),
+            Err(err) if err == vk::Result::ERROR_DEVICE_LOST => {
+                Err(Device::mark_lost(&this.device))
+            }
+            Err(err) => {
+                warn!("{err}");
+
+                Err(DriverError::OutOfMemory)
+            }
+        }
+    }
+}
+
+impl Deref for TimelineSemaphore {
+    type Target = vk::Semaphore;
+
+    fn deref(&self) -> &Self::Target {
+        &self.semaphore
+    }
+}
+
+impl Drop for TimelineSemaphore {
+    #[profiling::function]
+    fn drop(&mut self) {
+        if panicking() {
+            return;
+        }
+
+        unsafe {
+            self.device.destroy_semaphore(self.semaphore, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{graph::RenderGraph, pool::hash::HashPool},
+    };
+
+    fn create_test_semaphore() -> Option<(Arc<Device>, TimelineSemaphore)> {
+        let device = Device::create_test()?;
+
+        match TimelineSemaphore::create(&device, 0) {
+            Ok(semaphore) => Some((device, semaphore)),
+            Err(_) => {
+                eprintln!("skipping test: timeline semaphores are not supported");
+
+                None
+            }
+        }
+    }
+
+    #[test]
+    pub fn timeline_semaphore_host_signal() {
+        let Some((_, semaphore)) = create_test_semaphore() else {
+            return;
+        };
+
+        assert_eq!(TimelineSemaphore::value(&semaphore).unwrap(), 0);
+        assert!(!TimelineSemaphore::wait(&semaphore, 1, Duration::from_millis(1)).unwrap());
+
+        TimelineSemaphore::signal(&semaphore, 1).unwrap();
+
+        assert_eq!(TimelineSemaphore::value(&semaphore).unwrap(), 1);
+        assert!(TimelineSemaphore::wait(&semaphore, 1, Duration::ZERO).unwrap());
+
+        TimelineSemaphore::signal(&semaphore, 5).unwrap();
+
+        assert_eq!(TimelineSemaphore::value(&semaphore).unwrap(), 5);
+        assert!(TimelineSemaphore::wait(&semaphore, 3, Duration::ZERO).unwrap());
+    }
+
+    #[test]
+    pub fn timeline_semaphore_submit_signal() {
+        let Some((device, semaphore)) = create_test_semaphore() else {
+            return;
+        };
+        let semaphore = Arc::new(semaphore);
+        let mut pool = HashPool::new(&device);
+
+        let cmd_buf = RenderGraph::new()
+            .resolve()
+            .submit_signal(&mut pool, 0, 0, &semaphore, 2)
+            .unwrap();
+
+        assert!(TimelineSemaphore::wait(&semaphore, 2, Duration::from_secs(10)).unwrap());
+        assert_eq!(TimelineSemaphore::value(&semaphore).unwrap(), 2);
+
+        cmd_buf.wait_until_executed().unwrap();
+    }
+}
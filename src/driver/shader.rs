@@ -18,7 +18,7 @@ use {
         iter::repeat,
         mem::size_of_val,
         ops::Deref,
-        sync::Arc,
+        sync::{Arc, Weak},
         thread::panicking,
     },
 };
@@ -102,8 +102,8 @@ impl From<(u32, u32)> for DescriptorBinding {
 #[derive(Debug)]
 pub(crate) enum DescriptorInfo {
     AccelerationStructure(u32),
-    CombinedImageSampler(u32, Sampler, bool), //count, sampler, is-manually-defined?
-    InputAttachment(u32, u32),                //count, input index,
+    CombinedImageSampler(u32, Arc<Sampler>, bool), //count, sampler, is-manually-defined?
+    InputAttachment(u32, u32),                     //count, input index,
     SampledImage(u32),
     Sampler(u32),
     StorageBuffer(u32),
@@ -146,7 +146,7 @@ impl DescriptorInfo {
 
     pub fn sampler(&self) -> Option<&Sampler> {
         match self {
-            Self::CombinedImageSampler(_, sampler, _) => Some(sampler),
+            Self::CombinedImageSampler(_, sampler, _) => Some(sampler.as_ref()),
             _ => None,
         }
     }
@@ -169,7 +169,7 @@ impl DescriptorInfo {
 
 #[derive(Debug)]
 pub(crate) struct PipelineDescriptorInfo {
-    pub layouts: BTreeMap<u32, DescriptorSetLayout>,
+    pub layouts: BTreeMap<u32, Arc<DescriptorSetLayout>>,
     pub pool_sizes: HashMap<u32, HashMap<vk::DescriptorType, u32>>,
 }
 
@@ -254,7 +254,7 @@ impl PipelineDescriptorInfo {
 
             layouts.insert(
                 descriptor_set_idx,
-                DescriptorSetLayout::create(device, &create_info)?,
+                DescriptorSetLayout::create_cached(device, &create_info)?,
             );
         }
 
@@ -318,6 +318,36 @@ impl Sampler {
 
         Ok(Self { device, sampler })
     }
+
+    /// Returns a sampler matching `info`, which is shared with any other pipeline that currently
+    /// uses an identical sampler.
+    #[profiling::function]
+    pub fn create_cached(
+        device: &Arc<Device>,
+        info: impl Into<SamplerInfo>,
+    ) -> Result<Arc<Self>, DriverError> {
+        let info = info.into();
+
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut cache = device.sampler_cache.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut cache = cache.unwrap();
+
+        if let Some(sampler) = cache.get(&info).and_then(Weak::upgrade) {
+            trace!("create_cached: reusing {:?}", sampler.sampler);
+
+            return Ok(sampler);
+        }
+
+        let sampler = Arc::new(Self::create(device, info)?);
+
+        // Discard entries for samplers which are no longer used by any pipeline
+        cache.retain(|_, sampler| sampler.strong_count() > 0);
+        cache.insert(info, Arc::downgrade(&sampler));
+
+        Ok(sampler)
+    }
 }
 
 impl Debug for Sampler {
@@ -888,7 +918,7 @@ impl Shader {
 
                     DescriptorInfo::CombinedImageSampler(
                         binding_count,
-                        Sampler::create(device, sampler_info)?,
+                        Sampler::create_cached(device, sampler_info)?,
                         is_manually_defined,
                     )
                 }
@@ -1435,4 +1465,44 @@ mod tests {
 
         assert_eq!(info, builder);
     }
+
+    #[test]
+    pub fn sampler_create_cached() {
+        let Some(device) = Device::create_test() else {
+            return;
+        };
+
+        let cached_strong_count = |info: SamplerInfo| {
+            #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+            let cache = device.sampler_cache.lock();
+
+            #[cfg(not(feature = "parking_lot"))]
+            let cache = cache.unwrap();
+
+            cache.get(&info).map(Weak::strong_count)
+        };
+
+        let linear = Sampler::create_cached(&device, Info::LINEAR).unwrap();
+        let linear_again = Sampler::create_cached(&device, Info::LINEAR.build()).unwrap();
+        let nearest = Sampler::create_cached(&device, Info::NEAREST).unwrap();
+
+        assert!(Arc::ptr_eq(&linear, &linear_again));
+        assert!(!Arc::ptr_eq(&linear, &nearest));
+        assert_eq!(cached_strong_count(Info::LINEAR.build()), Some(2));
+
+        drop(linear);
+        drop(linear_again);
+
+        assert_eq!(cached_strong_count(Info::LINEAR.build()), Some(0));
+
+        // The dead entry is replaced by a new sampler, which is cached in turn
+        let linear = Sampler::create_cached(&device, Info::LINEAR).unwrap();
+
+        assert_eq!(Arc::strong_count(&linear), 1);
+        assert_eq!(cached_strong_count(Info::LINEAR.build()), Some(1));
+        assert!(Arc::ptr_eq(
+            &linear,
+            &Sampler::create_cached(&device, Info::LINEAR).unwrap()
+        ));
+    }
 }
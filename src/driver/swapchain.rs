@@ -116,6 +116,7 @@ impl Swapchain {
                 Err(SwapchainError::Suboptimal)
             }
             Err(err) if err == vk::Result::ERROR_DEVICE_LOST => {
+                Device::mark_lost(&self.device);
                 self.suboptimal = true;
 
                 Err(SwapchainError::DeviceLost)
@@ -218,9 +219,14 @@ impl Swapchain {
                 &present_info,
             ) {
                 Ok(_) => (),
+                Err(err) if err == vk::Result::ERROR_DEVICE_LOST => {
+                    Device::mark_lost(&self.device);
+
+                    // Handled in the next frame
+                    self.suboptimal = true;
+                }
                 Err(err)
-                    if err == vk::Result::ERROR_DEVICE_LOST
-                        || err == vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT
+                    if err == vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT
                         || err == vk::Result::ERROR_OUT_OF_DATE_KHR
                         || err == vk::Result::ERROR_SURFACE_LOST_KHR
                         || err == vk::Result::SUBOPTIMAL_KHR =>
@@ -13,9 +13,10 @@ use {
             image::{Image, ImageViewInfo},
             image_access_layout, is_framebuffer_access, is_read_access, is_write_access,
             pipeline_stage_access_flags, AttachmentInfo, AttachmentRef, CommandBuffer,
-            CommandBufferInfo, DescriptorBinding, DescriptorInfo, DescriptorPool,
+            CommandBufferInfo, DebugLabel, DescriptorBinding, DescriptorInfo, DescriptorPool,
             DescriptorPoolInfo, DescriptorSet, DriverError, FramebufferAttachmentImageInfo,
             FramebufferInfo, RenderPass, RenderPassInfo, SubpassDependency, SubpassInfo,
+            TimelineSemaphore,
         },
         pool::{Lease, Pool},
     },
@@ -29,6 +30,7 @@ use {
         collections::{HashMap, VecDeque},
         iter::repeat,
         ops::Range,
+        sync::Arc,
     },
     vk_sync::{cmd::pipeline_barrier, AccessType, BufferBarrier, GlobalBarrier, ImageBarrier},
 };
@@ -762,7 +764,9 @@ impl Resolver {
             .max()
             .unwrap_or_default();
         let max_sets = pass.execs.len() as u32 * (max_set_idx + 1);
+        // Descriptor sets are never freed individually; the pool is reset when leased again
         let mut info = DescriptorPoolInfo {
+            free_individual: false,
             max_sets,
             ..Default::default()
         };
@@ -2238,11 +2242,15 @@ impl Resolver {
         self.merge_scheduled_passes(&mut schedule.passes);
         self.lease_scheduled_resources(pool, &schedule.passes)?;
 
+        let device = Arc::clone(&cmd_buf.device);
+
         for pass_idx in schedule.passes.iter().copied() {
             let pass = &mut self.graph.passes[pass_idx];
 
             profiling::scope!("Pass", &pass.name);
 
+            let _label = DebugLabel::begin(&device, **cmd_buf, &pass.name, [0.0; 4]);
+
             let physical_pass = &mut self.physical_passes[pass_idx];
             let is_graphic = physical_pass.render_pass.is_some();
 
@@ -2694,10 +2702,57 @@ impl Resolver {
     /// Submits the remaining commands stored in this instance.
     #[profiling::function]
     pub fn submit<P>(
+        self,
+        pool: &mut P,
+        queue_family_index: usize,
+        queue_index: usize,
+    ) -> Result<Lease<CommandBuffer>, DriverError>
+    where
+        P: Pool<CommandBufferInfo, CommandBuffer>
+            + Pool<DescriptorPoolInfo, DescriptorPool>
+            + Pool<RenderPassInfo, RenderPass>,
+    {
+        trace!("submit");
+
+        self.submit_timeline(pool, queue_family_index, queue_index, None)
+    }
+
+    /// Submits the remaining commands stored in this instance and signals `semaphore` with `value`
+    /// once they have executed.
+    ///
+    /// `value` must be greater than any value previously signalled on `semaphore`. Other queues
+    /// and the host may wait for this value in order to depend on this submission without waiting
+    /// on the returned command buffer.
+    #[profiling::function]
+    pub fn submit_signal<P>(
+        self,
+        pool: &mut P,
+        queue_family_index: usize,
+        queue_index: usize,
+        semaphore: &Arc<TimelineSemaphore>,
+        value: u64,
+    ) -> Result<Lease<CommandBuffer>, DriverError>
+    where
+        P: Pool<CommandBufferInfo, CommandBuffer>
+            + Pool<DescriptorPoolInfo, DescriptorPool>
+            + Pool<RenderPassInfo, RenderPass>,
+    {
+        trace!("submit_signal: {value}");
+
+        self.submit_timeline(
+            pool,
+            queue_family_index,
+            queue_index,
+            Some((semaphore, value)),
+        )
+    }
+
+    fn submit_timeline<P>(
         mut self,
         pool: &mut P,
         queue_family_index: usize,
         queue_index: usize,
+        signal: Option<(&Arc<TimelineSemaphore>, u64)>,
     ) -> Result<Lease<CommandBuffer>, DriverError>
     where
         P: Pool<CommandBufferInfo, CommandBuffer>
@@ -2706,8 +2761,6 @@ impl Resolver {
     {
         use std::slice::from_ref;
 
-        trace!("submit");
-
         let mut cmd_buf = pool.lease(CommandBufferInfo::new(queue_family_index as _))?;
 
         debug_assert!(
@@ -2722,8 +2775,7 @@ impl Resolver {
         );
 
         unsafe {
-            Device::wait_for_fence(&cmd_buf.device, &cmd_buf.fence)
-                .map_err(|_| DriverError::OutOfMemory)?;
+            Device::wait_for_fence(&cmd_buf.device, &cmd_buf.fence)?;
 
             cmd_buf
                 .device
@@ -2746,14 +2798,35 @@ impl Resolver {
                 .device
                 .reset_fences(from_ref(&cmd_buf.fence))
                 .map_err(|_| DriverError::OutOfMemory)?;
+        }
+
+        let signal_semaphore = signal.map(|(semaphore, _)| ***semaphore);
+        let signal_value = signal.map(|(_, value)| value);
+        let mut timeline_info = vk::TimelineSemaphoreSubmitInfo::builder()
+            .signal_semaphore_values(signal_value.as_slice());
+        let mut submit_info = vk::SubmitInfo::builder()
+            .command_buffers(from_ref(&cmd_buf))
+            .signal_semaphores(signal_semaphore.as_slice());
+
+        if signal.is_some() {
+            submit_info = submit_info.push_next(&mut timeline_info);
+        }
+
+        unsafe {
             cmd_buf
                 .device
                 .queue_submit(
                     cmd_buf.device.queues[queue_family_index][queue_index],
-                    from_ref(&vk::SubmitInfo::builder().command_buffers(from_ref(&cmd_buf))),
+                    from_ref(&submit_info),
                     cmd_buf.fence,
                 )
-                .map_err(|_| DriverError::OutOfMemory)?;
+                .map_err(|err| {
+                    if err == vk::Result::ERROR_DEVICE_LOST {
+                        Device::mark_lost(&cmd_buf.device)
+                    } else {
+                        DriverError::OutOfMemory
+                    }
+                })?;
         }
 
         // This graph contains references to buffers, images, and other resources which must be kept
@@ -2763,6 +2836,11 @@ impl Resolver {
         // has been signalled.
         CommandBuffer::push_fenced_drop(&mut cmd_buf, self);
 
+        // The semaphore must not be destroyed before the device signals it
+        if let Some((semaphore, _)) = signal {
+            CommandBuffer::push_fenced_drop(&mut cmd_buf, Arc::clone(semaphore));
+        }
+
         Ok(cmd_buf)
     }
 
@@ -388,6 +388,7 @@ pub mod prelude {
                 fifo::FifoPool,
                 hash::HashPool,
                 lazy::LazyPool,
+                ring::BufferRing,
                 Lease, Pool, PoolInfo, PoolInfoBuilder,
             },
         },
@@ -172,7 +172,13 @@ impl Pool<DescriptorPoolInfo, DescriptorPool> for HashPool {
 
             cache.pop()
         }
-        .map(Ok)
+        .map(|item| {
+            if !item.info.free_individual {
+                DescriptorPool::reset(&item)?;
+            }
+
+            Ok(item)
+        })
         .unwrap_or_else(|| {
             debug!("Creating new {}", stringify!(DescriptorPool));
 
@@ -251,3 +257,44 @@ macro_rules! lease {
 lease!(AccelerationStructureInfo => AccelerationStructure, accel_struct_capacity);
 lease!(BufferInfo => Buffer, buffer_capacity);
 lease!(ImageInfo => Image, image_capacity);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn lease_descriptor_pool_free_individual() {
+        let Some(device) = Device::create_test() else {
+            return;
+        };
+        let mut pool = HashPool::new(&device);
+        let info = DescriptorPoolInfo {
+            max_sets: 1,
+            storage_buffer_count: 1,
+            ..Default::default()
+        };
+
+        let descriptor_pool = pool.lease(info.clone()).unwrap();
+
+        assert!(descriptor_pool.info.free_individual);
+
+        drop(descriptor_pool);
+
+        // A cached pool which frees sets individually must not be returned for reset pools
+        let descriptor_pool = pool
+            .lease(DescriptorPoolInfo {
+                free_individual: false,
+                ..info.clone()
+            })
+            .unwrap();
+
+        assert!(!descriptor_pool.info.free_individual);
+
+        drop(descriptor_pool);
+
+        // ...and the other way around
+        let descriptor_pool = pool.lease(info).unwrap();
+
+        assert!(descriptor_pool.info.free_individual);
+    }
+}
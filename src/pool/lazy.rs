@@ -227,7 +227,7 @@ impl Pool<BufferInfo, Buffer> for LazyPool {
 
         debug!("Creating new {}", stringify!(Buffer));
 
-        let item = Buffer::create(&self.device, info)?;
+        let item = Buffer::create(&self.device, self.info.buffer_info(info))?;
 
         Ok(Lease::new(cache_ref, item))
     }
@@ -280,7 +280,8 @@ impl Pool<DescriptorPoolInfo, DescriptorPool> for LazyPool {
             // Look for a compatible descriptor pool (has enough sets and descriptors)
             for idx in 0..cache.len() {
                 let item = unsafe { cache.get_unchecked(idx) };
-                if item.info.max_sets >= info.max_sets
+                if item.info.free_individual == info.free_individual
+                    && item.info.max_sets >= info.max_sets
                     && item.info.acceleration_structure_count >= info.acceleration_structure_count
                     && item.info.combined_image_sampler_count >= info.combined_image_sampler_count
                     && item.info.input_attachment_count >= info.input_attachment_count
@@ -295,6 +296,10 @@ impl Pool<DescriptorPoolInfo, DescriptorPool> for LazyPool {
                 {
                     let item = cache.swap_remove(idx);
 
+                    if !item.info.free_individual {
+                        DescriptorPool::reset(&item)?;
+                    }
+
                     return Ok(Lease::new(cache_ref, item));
                 }
             }
@@ -375,3 +380,44 @@ impl Pool<RenderPassInfo, RenderPass> for LazyPool {
         Ok(Lease::new(Arc::downgrade(cache_ref), item))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn lease_descriptor_pool_free_individual() {
+        let Some(device) = Device::create_test() else {
+            return;
+        };
+        let mut pool = LazyPool::new(&device);
+        let info = DescriptorPoolInfo {
+            max_sets: 1,
+            storage_buffer_count: 1,
+            ..Default::default()
+        };
+
+        let descriptor_pool = pool.lease(info.clone()).unwrap();
+
+        assert!(descriptor_pool.info.free_individual);
+
+        drop(descriptor_pool);
+
+        // A cached pool which frees sets individually must not be returned for reset pools
+        let descriptor_pool = pool
+            .lease(DescriptorPoolInfo {
+                free_individual: false,
+                ..info.clone()
+            })
+            .unwrap();
+
+        assert!(!descriptor_pool.info.free_individual);
+
+        drop(descriptor_pool);
+
+        // ...and the other way around
+        let descriptor_pool = pool.lease(info).unwrap();
+
+        assert!(descriptor_pool.info.free_individual);
+    }
+}
@@ -88,6 +88,7 @@ pub mod alias;
 pub mod fifo;
 pub mod hash;
 pub mod lazy;
+pub mod ring;
 
 use {
     crate::driver::{
@@ -98,11 +99,13 @@ use {
         image::{Image, ImageInfo, ImageInfoBuilder},
         CommandBuffer, DriverError,
     },
+    ash::vk,
     derive_builder::{Builder, UninitializedFieldError},
     std::{
         fmt::Debug,
         mem::ManuallyDrop,
         ops::{Deref, DerefMut},
+        ptr::drop_in_place,
         sync::{Arc, Weak},
         thread::panicking,
     },
@@ -155,6 +158,45 @@ impl<T> Lease<T> {
             item: ManuallyDrop::new(item),
         }
     }
+
+    /// Detaches the leased resource from its pool, returning the resource.
+    ///
+    /// The resource will not be returned to the pool when it is dropped and so it cannot be
+    /// discarded by calls such as `clear` or by the pool reaching capacity. This is useful for
+    /// ubiquitous resources, such as a default texture, which should live for the whole program.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::image::{ImageInfo};
+    /// # use screen_13::pool::{Lease, Pool};
+    /// # use screen_13::pool::lazy::{LazyPool};
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::new())?);
+    /// let mut pool = LazyPool::new(&device);
+    ///
+    /// let usage = vk::ImageUsageFlags::SAMPLED;
+    /// let info = ImageInfo::image_2d(1, 1, vk::Format::R8G8B8A8_UNORM, usage);
+    /// let white_image = Arc::new(Lease::detach(pool.lease(info)?));
+    ///
+    /// // The white image is unaffected by this call
+    /// pool.clear();
+    /// # Ok(()) }
+    /// ```
+    pub fn detach(this: Self) -> T {
+        let mut this = ManuallyDrop::new(this);
+
+        unsafe {
+            drop_in_place(&mut this.cache_ref);
+            ManuallyDrop::take(&mut this.item)
+        }
+    }
 }
 
 impl<T> AsRef<T> for Lease<T> {
@@ -269,6 +311,17 @@ pub struct PoolInfo {
     #[builder(default = "PoolInfo::DEFAULT_RESOURCE_CAPACITY", setter(strip_option))]
     pub buffer_capacity: usize,
 
+    /// The size, in bytes, which new buffers are rounded up to a multiple of. The default value is
+    /// `1`, which creates buffers of exactly the requested size.
+    ///
+    /// # Note
+    ///
+    /// Only pools which lease buffers larger than requested, such as
+    /// [`FifoPool`](self::fifo::FifoPool) and [`LazyPool`](self::lazy::LazyPool), use this value.
+    /// Larger values allow buffers of similar sizes to be reused at the cost of unused memory.
+    #[builder(default = "1")]
+    pub buffer_granularity: vk::DeviceSize,
+
     /// The maximum size of a single bucket of image resource instances. The default value is
     /// [`PoolInfo::DEFAULT_RESOURCE_CAPACITY`].
     ///
@@ -291,10 +344,19 @@ impl PoolInfo {
         Self {
             accel_struct_capacity: resource_capacity,
             buffer_capacity: resource_capacity,
+            buffer_granularity: 1,
             image_capacity: resource_capacity,
         }
     }
 
+    /// Returns `info` with a size rounded up to a multiple of [`PoolInfo::buffer_granularity`].
+    fn buffer_info(&self, mut info: BufferInfo) -> BufferInfo {
+        let granularity = self.buffer_granularity.max(1);
+        info.size = info.size.div_ceil(granularity) * granularity;
+
+        info
+    }
+
     fn default_cache<T>() -> Cache<T> {
         Cache::new(Mutex::new(Vec::with_capacity(
             Self::DEFAULT_RESOURCE_CAPACITY,
@@ -323,6 +385,7 @@ impl From<usize> for PoolInfo {
         Self {
             accel_struct_capacity: value,
             buffer_capacity: value,
+            buffer_granularity: 1,
             image_capacity: value,
         }
     }
@@ -345,3 +408,77 @@ impl From<UninitializedFieldError> for PoolInfoBuilderError {
         Self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_items(cache: &Cache<u32>) -> Vec<u32> {
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let cache = cache.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let cache = cache.unwrap();
+
+        cache.clone()
+    }
+
+    #[test]
+    pub fn lease_drop_returns_item() {
+        let cache: Cache<u32> = PoolInfo::explicit_cache(4);
+        let lease = Lease::new(Arc::downgrade(&cache), 42);
+
+        assert_eq!(*lease, 42);
+        assert!(cache_items(&cache).is_empty());
+
+        drop(lease);
+
+        assert_eq!(cache_items(&cache), [42]);
+    }
+
+    #[test]
+    pub fn lease_detach() {
+        let cache: Cache<u32> = PoolInfo::explicit_cache(4);
+        let lease = Lease::new(Arc::downgrade(&cache), 42);
+        let item = Lease::detach(lease);
+
+        assert_eq!(item, 42);
+        assert!(cache_items(&cache).is_empty());
+
+        // The pool is no longer referenced by the detached item
+        assert_eq!(Arc::weak_count(&cache), 0);
+    }
+
+    #[test]
+    pub fn pool_info_buffer_granularity() {
+        let info = PoolInfo {
+            buffer_granularity: 4096,
+            ..PoolInfo::default()
+        };
+
+        for (size, expected) in [(1, 4096), (4096, 4096), (5000, 8192), (6000, 8192)] {
+            let buffer_info = info.buffer_info(BufferInfo::host_mem(
+                size,
+                vk::BufferUsageFlags::STORAGE_BUFFER,
+            ));
+
+            assert_eq!(buffer_info.size, expected);
+        }
+    }
+
+    #[test]
+    pub fn pool_info_buffer_granularity_identity() {
+        let info = PoolInfo::default();
+
+        assert_eq!(info.buffer_granularity, 1);
+
+        for size in [1, 5000, 6000, 8193] {
+            let buffer_info = info.buffer_info(BufferInfo::host_mem(
+                size,
+                vk::BufferUsageFlags::STORAGE_BUFFER,
+            ));
+
+            assert_eq!(buffer_info.size, size);
+        }
+    }
+}
@@ -0,0 +1,336 @@
+//! Ring-buffer suballocation of a single host-visible buffer.
+
+use {
+    super::Lease,
+    crate::{
+        driver::{
+            buffer::{Buffer, BufferInfo},
+            device::Device,
+            CommandBuffer, DriverError,
+        },
+        graph::{node::BufferNode, RenderGraph},
+    },
+    ash::vk,
+    log::warn,
+    std::{collections::VecDeque, ops::Range, ptr::copy_nonoverlapping, sync::Arc},
+};
+
+/// A suballocator which hands out small ranges of a single large host-visible buffer.
+///
+/// Leasing a separate buffer for each small uniform or storage upload fragments memory and causes
+/// the pool to churn. A `BufferRing` instead copies data into consecutive ranges of one buffer and
+/// reclaims those ranges once the command buffers which used them have executed.
+///
+/// # Details
+///
+/// * Each range is aligned to the minimum offset alignment required by the buffer usage
+/// * Ranges pushed between calls to [`BufferRing::finish_frame`] are reclaimed together
+/// * When the ring is full, pushing data waits for the oldest frame to finish executing
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use ash::vk;
+/// # use screen_13::driver::DriverError;
+/// # use screen_13::driver::device::{Device, DeviceInfo};
+/// # use screen_13::graph::RenderGraph;
+/// # use screen_13::pool::hash::HashPool;
+/// # use screen_13::pool::ring::BufferRing;
+/// # fn main() -> Result<(), DriverError> {
+/// # let device = Arc::new(Device::create_headless(DeviceInfo::new())?);
+/// # let mut pool = HashPool::new(&device);
+/// let mut ring = BufferRing::new(&device, 65_536, vk::BufferUsageFlags::UNIFORM_BUFFER)?;
+/// let mut render_graph = RenderGraph::new();
+///
+/// // SAFETY: The ring buffer is not unbound from the graph or otherwise borrowed
+/// let range = unsafe { ring.push(&[0u8; 64])? };
+/// let buffer = ring.bind_node(&mut render_graph);
+///
+/// // (Bind the buffer to a pass using read_descriptor_as(.., buffer, range))
+///
+/// let cmd_buf = render_graph.resolve().submit(&mut pool, 0, 0)?;
+/// ring.finish_frame(cmd_buf);
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct BufferRing {
+    alignment: vk::DeviceSize,
+    buffer: Arc<Buffer>,
+    cmd_bufs: VecDeque<Lease<CommandBuffer>>,
+    cursor: RingCursor,
+}
+
+impl BufferRing {
+    /// Constructs a new `BufferRing` backed by a host-visible buffer of the given size.
+    pub fn new(
+        device: &Arc<Device>,
+        size: vk::DeviceSize,
+        usage: vk::BufferUsageFlags,
+    ) -> Result<Self, DriverError> {
+        let limits = &device.physical_device.properties_v1_0.limits;
+        let mut alignment = 1;
+
+        if usage.contains(vk::BufferUsageFlags::UNIFORM_BUFFER) {
+            alignment = alignment.max(limits.min_uniform_buffer_offset_alignment);
+        }
+
+        if usage.contains(vk::BufferUsageFlags::STORAGE_BUFFER) {
+            alignment = alignment.max(limits.min_storage_buffer_offset_alignment);
+        }
+
+        let buffer = Arc::new(Buffer::create(device, BufferInfo::host_mem(size, usage))?);
+
+        Ok(Self {
+            alignment,
+            buffer,
+            cmd_bufs: Default::default(),
+            cursor: RingCursor::new(size),
+        })
+    }
+
+    /// Binds the buffer which backs all ranges of this ring to `render_graph`.
+    ///
+    /// See [`BufferRing::push`] for the requirements placed on the returned node.
+    pub fn bind_node(&self, render_graph: &mut RenderGraph) -> BufferNode {
+        render_graph.bind_node(&self.buffer)
+    }
+
+    /// Returns the Vulkan handle of the buffer which backs all ranges of this ring.
+    pub fn handle(&self) -> vk::Buffer {
+        **self.buffer
+    }
+
+    /// Ends the current frame.
+    ///
+    /// All ranges pushed since the previous call are reclaimed once `cmd_buf` has executed.
+    pub fn finish_frame(&mut self, cmd_buf: Lease<CommandBuffer>) {
+        if self.cursor.finish_frame() {
+            self.cmd_bufs.push_back(cmd_buf);
+        }
+    }
+
+    /// Copies `data` into the ring and returns the range of the buffer which now holds it.
+    ///
+    /// If there is not enough space this function blocks until the oldest frame has executed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DriverError::InvalidData`] if `data` is larger than the ring and
+    /// [`DriverError::OutOfMemory`] if the data pushed during the current frame fills the ring.
+    ///
+    /// # Safety
+    ///
+    /// The ring writes into its buffer through a raw pointer. Callers must not hold any borrow of
+    /// the mapped memory of the buffer while pushing, such as a slice returned by
+    /// [`Buffer::mapped_slice`] after unbinding a node returned by [`BufferRing::bind_node`].
+    pub unsafe fn push(&mut self, data: &[u8]) -> Result<Range<vk::DeviceSize>, DriverError> {
+        let len = data.len() as vk::DeviceSize;
+
+        debug_assert_ne!(len, 0, "Data must be non-empty");
+
+        if len > self.cursor.capacity {
+            warn!("data is larger than ring");
+
+            return Err(DriverError::InvalidData);
+        }
+
+        // Reclaim any frames which have already executed
+        while let Some(cmd_buf) = self.cmd_bufs.front() {
+            if !cmd_buf.has_executed()? {
+                break;
+            }
+
+            self.cmd_bufs.pop_front();
+            self.cursor.release_frame();
+        }
+
+        let start = loop {
+            if let Some(start) = self.cursor.allocate(len, self.alignment) {
+                break start;
+            }
+
+            // Wait for the oldest frame so that its ranges may be reused
+            let cmd_buf = self.cmd_bufs.pop_front().ok_or_else(|| {
+                warn!("ring is full");
+
+                DriverError::OutOfMemory
+            })?;
+            cmd_buf.wait_until_executed()?;
+            self.cursor.release_frame();
+        };
+
+        // Ranges returned by the cursor never overlap a range the device may be reading and the
+        // caller guarantees nothing on the host is borrowing the mapped memory
+        copy_nonoverlapping(
+            data.as_ptr(),
+            Buffer::mapped_ptr(&self.buffer).add(start as _),
+            data.len(),
+        );
+
+        Ok(start..start + len)
+    }
+}
+
+/// Tracks which parts of a ring are in use without reference to any buffer.
+#[derive(Debug)]
+struct RingCursor {
+    capacity: vk::DeviceSize,
+    current_frame: Option<vk::DeviceSize>,
+    frames: VecDeque<vk::DeviceSize>,
+    head: vk::DeviceSize,
+}
+
+impl RingCursor {
+    fn new(capacity: vk::DeviceSize) -> Self {
+        Self {
+            capacity,
+            current_frame: None,
+            frames: Default::default(),
+            head: 0,
+        }
+    }
+
+    /// Returns the start of a free range of `len` bytes, or `None` if the ring is full.
+    fn allocate(
+        &mut self,
+        len: vk::DeviceSize,
+        alignment: vk::DeviceSize,
+    ) -> Option<vk::DeviceSize> {
+        let start = match self.frames.front().copied().or(self.current_frame) {
+            None if len <= self.capacity => 0,
+            None => return None,
+            Some(tail) => {
+                let start = align_up(self.head, alignment);
+
+                // The end of a range never reaches the tail of the ring; this is how a wrapped
+                // head is told apart from an empty ring
+                if self.head >= tail {
+                    if start + len <= self.capacity {
+                        start
+                    } else if len < tail {
+                        0
+                    } else {
+                        return None;
+                    }
+                } else if start + len < tail {
+                    start
+                } else {
+                    return None;
+                }
+            }
+        };
+
+        self.current_frame.get_or_insert(start);
+        self.head = start + len;
+
+        Some(start)
+    }
+
+    /// Returns `true` if any ranges were allocated during the finished frame.
+    fn finish_frame(&mut self) -> bool {
+        if let Some(start) = self.current_frame.take() {
+            self.frames.push_back(start);
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn release_frame(&mut self) {
+        self.frames.pop_front();
+    }
+}
+
+fn align_up(val: vk::DeviceSize, atom: vk::DeviceSize) -> vk::DeviceSize {
+    (val + atom - 1) / atom * atom
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn ring_cursor_reuses_frames() {
+        let mut cursor = RingCursor::new(256);
+
+        assert_eq!(cursor.allocate(16, 64), Some(0));
+        assert_eq!(cursor.allocate(16, 64), Some(64));
+        assert!(cursor.finish_frame());
+
+        // Nothing is in flight once the frame is released, so the ring starts over
+        cursor.release_frame();
+
+        assert_eq!(cursor.allocate(16, 64), Some(0));
+    }
+
+    #[test]
+    pub fn ring_cursor_wraps() {
+        let mut cursor = RingCursor::new(256);
+
+        assert_eq!(cursor.allocate(128, 1), Some(0));
+        assert!(cursor.finish_frame());
+        assert_eq!(cursor.allocate(96, 1), Some(128));
+        assert!(cursor.finish_frame());
+
+        // The first frame is still in flight
+        assert_eq!(cursor.allocate(64, 1), None);
+
+        cursor.release_frame();
+
+        // The end of the ring is too small, so the range wraps to the start
+        assert_eq!(cursor.allocate(64, 1), Some(0));
+        assert_eq!(cursor.allocate(64, 1), None);
+        assert_eq!(cursor.allocate(63, 1), Some(64));
+    }
+
+    #[test]
+    pub fn buffer_ring_push() {
+        use crate::{graph::RenderGraph, pool::hash::HashPool};
+
+        let Some(device) = Device::create_test() else {
+            return;
+        };
+        let mut pool = HashPool::new(&device);
+        let mut ring =
+            BufferRing::new(&device, 1024, vk::BufferUsageFlags::STORAGE_BUFFER).unwrap();
+
+        let first = unsafe { ring.push(&[1; 16]) }.unwrap();
+        let second = unsafe { ring.push(&[2; 16]) }.unwrap();
+
+        assert_eq!(first.start, 0);
+        assert!(second.start >= first.end);
+        assert_eq!(second.start % ring.alignment, 0);
+
+        {
+            let data = Buffer::mapped_slice(&ring.buffer);
+
+            assert_eq!(&data[first.start as usize..first.end as usize], &[1; 16]);
+            assert_eq!(&data[second.start as usize..second.end as usize], &[2; 16]);
+        }
+
+        let mut render_graph = RenderGraph::new();
+        ring.bind_node(&mut render_graph);
+        ring.finish_frame(render_graph.resolve().submit(&mut pool, 0, 0).unwrap());
+
+        // There is no room after the first frame, so the ring waits for it and starts over
+        let third = unsafe { ring.push(&[3; 1000]) }.unwrap();
+
+        assert_eq!(third, 0..1000);
+        assert_eq!(
+            &Buffer::mapped_slice(&ring.buffer)[0..1000],
+            [3; 1000].as_slice()
+        );
+    }
+
+    #[test]
+    pub fn ring_cursor_empty_frame() {
+        let mut cursor = RingCursor::new(256);
+
+        assert!(!cursor.finish_frame());
+        assert_eq!(cursor.allocate(257, 1), None);
+    }
+}